@@ -2,7 +2,7 @@ use charbs::{
   app::Commands,
   materials::{ColorMaterial, DefaultMaterials},
   math::Rectangle,
-  mesh::{Mesh, MeshInstance},
+  mesh::{InstanceHandle, Mesh, MeshInstance},
   prelude::*,
   renderer::RendererModule,
   resources::Resources,
@@ -16,18 +16,29 @@ fn main() {
     .add_module(RendererModule)
     .add_module(DefaultMaterials)
     .add_handler(Init, init)
+    .add_handler(Update, spin)
     .run();
 }
 
+struct Square(InstanceHandle<ColorMaterial>);
+
 pub fn init(
   ctx: Res<RenderContext>,
   mut commands: ResMut<Commands>,
   mut meshes: ResMut<Resources<Mesh>>,
   mut materials: ResMut<Resources<ColorMaterial>>,
 ) {
-  commands.spawn(MeshInstance {
+  let square = commands.spawn(MeshInstance {
     mesh: meshes.add(Rectangle::new(0.5, 0.5)),
     material: materials.add(ColorMaterial::new(ctx.device(), 0.0, 0.0, 1.0)),
     transform: Transform::default(),
   });
+
+  commands.add_state(Square(square));
+}
+
+fn spin(square: Res<Square>, time: Res<Time>, mut transforms: Query<&mut Transform>) {
+  if let Some(transform) = square.0.transform_mut(&mut transforms) {
+    transform.rotation += time.delta_secs();
+  }
 }
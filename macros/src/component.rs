@@ -0,0 +1,16 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub(crate) fn impl_component(ast: &DeriveInput) -> TokenStream {
+  let name = &ast.ident;
+  let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+  let gen = quote! {
+    impl #impl_generics ::charbs::ecs::Component for #name #ty_generics #where_clause {}
+  };
+
+  gen.into()
+}
@@ -0,0 +1,104 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericParam};
+
+pub(crate) fn impl_handler_param(ast: &DeriveInput) -> TokenStream {
+  let name = &ast.ident;
+
+  let fields = match &ast.data {
+    Data::Struct(data) => &data.fields,
+    _ => {
+      return syn::Error::new_spanned(
+        name,
+        "HandlerParam can only be derived for structs",
+      )
+      .to_compile_error()
+      .into();
+    }
+  };
+
+  let lifetimes = ast.generics.lifetimes().count();
+
+  if lifetimes > 1 {
+    return syn::Error::new_spanned(
+      &ast.generics,
+      "HandlerParam can only be derived for structs with at most one lifetime",
+    )
+    .to_compile_error()
+    .into();
+  }
+
+  // Replace the lifetime of the structure with the one of the retrieved item.
+  let item_generics = ast.generics.params.iter().map(|param| match param {
+    GenericParam::Lifetime(_) => quote! { '__item },
+    GenericParam::Type(param) => {
+      let ident = &param.ident;
+      quote! { #ident }
+    }
+    GenericParam::Const(param) => {
+      let ident = &param.ident;
+      quote! { #ident }
+    }
+  });
+
+  let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+  let storages: Vec<_> = (0..types.len())
+    .map(|index| syn::Ident::new(&format!("storage_{}", index), name.span()))
+    .collect();
+
+  // Build the item from the parameters of each field.
+  let item = match fields {
+    Fields::Named(_) => {
+      let idents = fields.iter().map(|field| &field.ident);
+
+      quote! {
+        #name {
+          #(#idents: <#types as ::charbs::state::HandlerParam>::retrieve(
+            #storages,
+            structures,
+            ticks,
+          ),)*
+        }
+      }
+    }
+    Fields::Unnamed(_) => quote! {
+      #name(
+        #(<#types as ::charbs::state::HandlerParam>::retrieve(#storages, structures, ticks),)*
+      )
+    },
+    Fields::Unit => quote! { #name },
+  };
+
+  let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+  let gen = quote! {
+    impl #impl_generics ::charbs::state::HandlerParam for #name #ty_generics #where_clause {
+      type Item<'__item> = #name<#(#item_generics),*>;
+
+      type Storage = (#(<#types as ::charbs::state::HandlerParam>::Storage,)*);
+
+      fn init() -> Self::Storage {
+        (#(<#types as ::charbs::state::HandlerParam>::init(),)*)
+      }
+
+      #[allow(unused_variables)]
+      fn retrieve<'__item>(
+        storage: &'__item mut Self::Storage,
+        structures: &'__item ::charbs::state::Structures,
+        ticks: ::charbs::state::Ticks,
+      ) -> Self::Item<'__item> {
+        let (#(#storages,)*) = storage;
+
+        #item
+      }
+
+      fn access(access: &mut ::charbs::state::Access) {
+        #(<#types as ::charbs::state::HandlerParam>::access(access);)*
+      }
+    }
+  };
+
+  gen.into()
+}
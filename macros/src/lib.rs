@@ -3,7 +3,9 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+mod component;
 mod event;
+mod handler_param;
 mod schedule;
 
 #[proc_macro_derive(ScheduleLabel)]
@@ -23,3 +25,21 @@ pub fn event_derive(input: TokenStream) -> TokenStream {
   // Build the impl
   crate::event::impl_event(&input)
 }
+
+#[proc_macro_derive(HandlerParam)]
+pub fn handler_param_derive(input: TokenStream) -> TokenStream {
+  // Parse the input tokens into a syntax tree
+  let input = parse_macro_input!(input as DeriveInput);
+
+  // Build the impl
+  crate::handler_param::impl_handler_param(&input)
+}
+
+#[proc_macro_derive(Component)]
+pub fn component_derive(input: TokenStream) -> TokenStream {
+  // Parse the input tokens into a syntax tree
+  let input = parse_macro_input!(input as DeriveInput);
+
+  // Build the impl
+  crate::component::impl_component(&input)
+}
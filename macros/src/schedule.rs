@@ -1,14 +1,103 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::DeriveInput;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+/// Builds the pattern binding the fields of a struct or variant along with the
+/// expression formatting them with [`std::fmt::Debug`], so that labels with the
+/// same type but different values get different names.
+fn describe_fields(fields: &Fields) -> (impl ToTokens, impl ToTokens) {
+  match fields {
+    Fields::Unit => (quote! {}, quote! { ::std::string::String::new() }),
+    Fields::Unnamed(fields) => {
+      let bindings: Vec<_> = (0..fields.unnamed.len())
+        .map(|index| format_ident!("field_{}", index))
+        .collect();
+      let format = vec!["{:?}"; bindings.len()].join(", ");
+
+      (
+        quote! { (#(#bindings),*) },
+        quote! { ::std::format!(concat!("(", #format, ")"), #(#bindings),*) },
+      )
+    }
+    Fields::Named(fields) => {
+      let idents: Vec<_> = fields
+        .named
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect();
+      let format = idents
+        .iter()
+        .map(|ident| format!("{}: {{:?}}", ident))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+      (
+        quote! { { #(#idents),* } },
+        quote! { ::std::format!(concat!(" {{ ", #format, " }}"), #(#idents),*) },
+      )
+    }
+  }
+}
 
 pub(crate) fn impl_schedule_label(ast: &DeriveInput) -> TokenStream {
   let name = &ast.ident;
 
+  // Unit structs are identified by their type, anything else by its value.
+  let key = match &ast.data {
+    Data::Struct(data) if matches!(data.fields, Fields::Unit) => quote! {
+      ::charbs::state::ScheduleKey::of::<Self>()
+    },
+    Data::Union(_) => {
+      return syn::Error::new_spanned(
+        name,
+        "ScheduleLabel can only be derived for structs and enums",
+      )
+      .to_compile_error()
+      .into();
+    }
+    Data::Enum(data) => {
+      let arms = data.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let prefix = ident.to_string();
+        let (pattern, fields) = describe_fields(&variant.fields);
+
+        quote! { Self::#ident #pattern => format!("{}{}", #prefix, #fields) }
+      });
+
+      // Name enum labels after their variant and its fields to tell their
+      // schedules apart.
+      quote! {
+        let variant = match self {
+          #(#arms,)*
+        };
+
+        ::charbs::state::ScheduleKey::new(::std::clone::Clone::clone(self))
+          .with_name(format!("{}::{}", ::std::any::type_name::<Self>(), variant))
+      }
+    }
+    Data::Struct(data) => {
+      let (pattern, fields) = describe_fields(&data.fields);
+
+      // Name struct labels after their fields to tell their schedules apart.
+      quote! {
+        let Self #pattern = self;
+
+        ::charbs::state::ScheduleKey::new(::std::clone::Clone::clone(self))
+          .with_name(format!("{}{}", ::std::any::type_name::<Self>(), #fields))
+      }
+    }
+  };
+
+  let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
   let gen = quote! {
-    impl ScheduleLabel for #name {}
+    impl #impl_generics ::charbs::state::ScheduleLabel for #name #ty_generics #where_clause {
+      fn key(&self) -> ::charbs::state::ScheduleKey {
+        #key
+      }
+    }
   };
 
   gen.into()
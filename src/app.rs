@@ -1,9 +1,17 @@
 use crate::{
+  app_state::{self, AppState, NextState, States},
   assets::Assets,
+  ecs::{Bundle, Entities, Entity},
   events::EventBus,
+  introspection::ScheduleInfo,
   materials::{Material, MeshInstancesToSpawn},
-  mesh::MeshInstance,
-  state::{Handler, IntoHandler, ResMut, ScheduleLabel, Scheduler, State},
+  mesh::{InstanceHandle, MeshInstance, Visibility},
+  state::{
+    HandlerId, HandlerSet, IntoHandlerConfig, ResMut, ScheduleError, ScheduleKey,
+    ScheduleLabel, Scheduler, State,
+  },
+  time::{self, FixedTime, Time},
+  transform::{self, GlobalTransform},
 };
 
 use std::sync::{Arc, Mutex};
@@ -17,10 +25,125 @@ pub struct PreInit;
 #[derive(ScheduleLabel)]
 pub struct Init;
 
+/// A schedule label that represents the schedule running right before the
+/// application update schedule.
+#[derive(ScheduleLabel)]
+pub struct PreUpdate;
+
+/// A schedule label that represents the application fixed update schedule.
+///
+/// It runs between [`PreUpdate`] and [`Update`], as many times as needed for
+/// it to run at the fixed rate configured in the [`FixedTime`] structure of the
+/// state, which makes it suited to frame-rate independent simulation.
+#[derive(ScheduleLabel)]
+pub struct FixedUpdate;
+
+/// A private schedule label for the main schedule running [`FixedUpdate`].
+#[derive(ScheduleLabel)]
+struct RunFixedUpdate;
+
+/// A private schedule label for the first main schedule, updating [`Time`].
+#[derive(ScheduleLabel)]
+struct UpdateTime;
+
+/// A private schedule label for the main schedule applying the transitions of
+/// every [`AppState`].
+#[derive(ScheduleLabel)]
+struct StateTransition;
+
+/// A private schedule label for the main schedule running the
+/// [`app_state::OnUpdate`] schedules of every [`AppState`].
+#[derive(ScheduleLabel)]
+struct RunOnUpdate;
+
 /// A schedule label that represents the application update schedule.
 #[derive(ScheduleLabel)]
 pub struct Update;
 
+/// A schedule label that represents the schedule running right after the
+/// application update schedule.
+#[derive(ScheduleLabel)]
+pub struct PostUpdate;
+
+/// A schedule label that represents the last schedule of every iteration of
+/// the application main loop.
+#[derive(ScheduleLabel)]
+pub struct Last;
+
+/// A structure holding the ordered list of schedules the application runs on
+/// every iteration of its main loop.
+///
+/// It contains the schedule updating [`Time`], [`PreUpdate`], the schedule
+/// applying state transitions, the schedule running [`FixedUpdate`], [`Update`],
+/// the schedule running state updates, [`PostUpdate`] and [`Last`] by default.
+/// Other schedules can be inserted relative to the existing ones through
+/// [`App::add_schedule_before`] and [`App::add_schedule_after`].
+struct MainSchedules {
+  labels: Vec<(ScheduleKey, &'static str)>,
+}
+
+impl Default for MainSchedules {
+  fn default() -> Self {
+    Self {
+      labels: vec![
+        Self::entry(&UpdateTime),
+        Self::entry(&PreUpdate),
+        Self::entry(&StateTransition),
+        Self::entry(&RunFixedUpdate),
+        Self::entry(&Update),
+        Self::entry(&RunOnUpdate),
+        Self::entry(&PostUpdate),
+        Self::entry(&Last),
+      ],
+    }
+  }
+}
+
+impl MainSchedules {
+  /// Returns the entry representing a schedule label within the list.
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The schedule label.
+  fn entry<R: ScheduleLabel + 'static>(label: &R) -> (ScheduleKey, &'static str) {
+    (label.key(), std::any::type_name::<R>())
+  }
+
+  /// Inserts a schedule label at an offset from the position of an existing
+  /// one. Panics if the existing label is not part of the list or if the new
+  /// one already is.
+  ///
+  /// # Arguments
+  ///
+  /// * `existing` - The label already in the list.
+  /// * `label` - The label to insert.
+  /// * `offset` - The offset from the existing label, `0` for before it and `1`
+  ///   for after it.
+  fn insert<E: ScheduleLabel + 'static, R: ScheduleLabel + 'static>(
+    &mut self,
+    existing: &E,
+    label: &R,
+    offset: usize,
+  ) {
+    let (existing, existing_name) = Self::entry(existing);
+    let (key, name) = Self::entry(label);
+
+    assert!(
+      !self.labels.iter().any(|(label, _)| *label == key),
+      "Schedule {} is already a main schedule.",
+      name
+    );
+
+    let index = self
+      .labels
+      .iter()
+      .position(|(label, _)| *label == existing)
+      .unwrap_or_else(|| panic!("Schedule {} is not a main schedule.", existing_name));
+
+    self.labels.insert(index + offset, (key, name));
+  }
+}
+
 /// A type alias for a function that takes an application and runs it.
 type RunnerFn = fn(App);
 
@@ -35,7 +158,7 @@ fn default_runner(mut app: App) {
   app.run_schedule(Init);
 
   loop {
-    app.run_schedule(Update);
+    app.run_main_schedules();
     app.run_post_loop();
   }
 }
@@ -43,12 +166,13 @@ fn default_runner(mut app: App) {
 /// A structure representing an application.
 ///
 /// This encapsulates all convenience wrappers around a global application
-/// [`State`] and a runtime [`Scheduler`] to execute [`Handler`]s.
+/// [`State`] and a runtime [`Scheduler`] to execute [`Handler`](crate::state::Handler)s.
 pub struct App {
   // TODO: Used to provide Window apps easy access to state.
   // This should be replaced by something more elegant.
   pub(crate) state: Arc<Mutex<State>>,
   scheduler: Arc<Mutex<Scheduler>>,
+  main_schedules: MainSchedules,
   runner: RunnerFn,
 }
 
@@ -58,6 +182,7 @@ impl Default for App {
     Self {
       state: Default::default(),
       scheduler: Default::default(),
+      main_schedules: Default::default(),
       runner: default_runner,
     }
   }
@@ -75,12 +200,28 @@ impl App {
     app.add_state(Assets::default());
     app.add_state(Commands::default());
     app.add_state(EventBus::default());
+    app.init_state::<Entities>();
+    app.init_state::<FixedTime>();
+    app.add_state(Time::default());
+
+    // Keep track of time and drive the fixed update schedule.
+    app.add_handler(UpdateTime, time::update_time);
+    app.add_handler(RunFixedUpdate, time::run_fixed_update);
+
+    // Propagate the transforms of entities to their children.
+    app.add_handler(
+      PostUpdate,
+      transform::propagate_transforms.in_set("transforms"),
+    );
 
     // Up, up and away!
     (runner)(app);
   }
 
-  /// Sets the runner function for the application.
+  /// Sets the runner function for the application, which is given the
+  /// initialized application by [`App::run`] and drives its main loop with
+  /// [`App::run_schedule`], [`App::run_main_schedules`] and
+  /// [`App::run_post_loop`].
   ///
   /// # Arguments
   ///
@@ -88,57 +229,71 @@ impl App {
   ///
   /// * `->` - A mutable reference to the [`App`].
   #[inline]
-  pub(crate) fn set_runner(&mut self, runner: RunnerFn) -> &mut Self {
+  pub fn set_runner(&mut self, runner: RunnerFn) -> &mut Self {
     self.runner = runner;
 
     self
   }
 
-  /// Internal thread-safe method to run a specific schedule in the
-  /// [`Scheduler`].
+  /// Thread-safe method to run a specific schedule in the [`Scheduler`] and
+  /// apply the [`Commands`] it queued.
   ///
   /// # Arguments
   ///
   /// * `label` - The schedule label to run.
   #[inline]
-  pub(crate) fn run_schedule<R: ScheduleLabel + 'static>(&self, label: R) {
+  pub fn run_schedule<R: ScheduleLabel + 'static>(&self, label: R) {
+    self.run_schedule_by_key(label.key());
+  }
+
+  /// Runs every schedule of the application main loop in order.
+  #[inline]
+  pub fn run_main_schedules(&self) {
+    for (key, _) in self.main_schedules.labels.iter() {
+      self.run_schedule_by_key(key.clone());
+    }
+  }
+
+  /// Internal thread-safe method to run a specific schedule in the
+  /// [`Scheduler`] and apply the [`Commands`] it queued.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key of the schedule label to run.
+  fn run_schedule_by_key(&self, key: ScheduleKey) {
     if let Ok(mut scheduler) = self.scheduler.try_lock() {
       if let Ok(mut state) = self.state.try_lock() {
-        scheduler.run(label, &mut state);
-
-        // Take the state from the commands structure and merge the new state into the existing state.
-        let mut new_state = std::mem::take(&mut state.get::<ResMut<Commands>>().state);
-        state.merge(&mut new_state);
+        scheduler.run_by_key(key, &mut state);
+        Commands::apply_queued(&mut state, &mut scheduler);
       }
     }
   }
 
-  /// Runs the post-loop logic for the application.
-  ///
-  /// Execute all commands queued in the [`Commands`] struct and reset the
-  /// [`EventBus`] for the next iteration.
+  /// Runs the post-loop logic for the application, clearing the [`EventBus`]
+  /// for the next iteration. Queued [`Commands`] are already applied after
+  /// each schedule.
   #[inline]
-  pub(crate) fn run_post_loop(&mut self) {
+  pub fn run_post_loop(&mut self) {
     // Reset the event bus.
     if let Ok(state) = self.state.try_lock() {
       state.get::<ResMut<EventBus>>().clear();
     }
   }
 
-  /// Add a [`Handler`] to a specfic schedule in the application's
+  /// Add a [`Handler`](crate::state::Handler) to a specfic schedule in the application's
   /// [`Scheduler`].
   ///
   /// # Arguments
   ///
-  /// * `label` - The [`ScheduleLabel`] to add the [`Handler`] to.
-  /// * `handler` - The [`Handler`] to add to the schedule.
+  /// * `label` - The [`ScheduleLabel`] to add the [`Handler`](crate::state::Handler) to.
+  /// * `handler` - The [`Handler`](crate::state::Handler) to add to the schedule.
   ///
   /// * `->` - A mutable reference to the [`App`].
   #[inline]
-  pub fn add_handler<R: ScheduleLabel + 'static, I, S: Handler + 'static>(
+  pub fn add_handler<R: ScheduleLabel + 'static, I>(
     &mut self,
     label: R,
-    handler: impl IntoHandler<I, Handler = S>,
+    handler: impl IntoHandlerConfig<I>,
   ) -> &mut Self {
     if let Ok(mut scheduler) = self.scheduler.try_lock() {
       scheduler.add_handler(label, handler);
@@ -147,6 +302,87 @@ impl App {
     self
   }
 
+  /// Configure the ordering constraints of a named handler set in a specific
+  /// schedule of the application's [`Scheduler`].
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The [`ScheduleLabel`] of the schedule containing the set.
+  /// * `set` - The [`HandlerSet`] configuration.
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn configure_set<R: ScheduleLabel + 'static>(
+    &mut self,
+    label: R,
+    set: HandlerSet,
+  ) -> &mut Self {
+    if let Ok(mut scheduler) = self.scheduler.try_lock() {
+      scheduler.configure_set(label, set);
+    }
+
+    self
+  }
+
+  /// Describe every schedule of the application's [`Scheduler`] for debugging
+  /// purposes. See [`Scheduler::describe`].
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The descriptions of the schedules, sorted by name.
+  pub fn describe_schedules(&self) -> Result<Vec<ScheduleInfo>, ScheduleError> {
+    match self.scheduler.try_lock() {
+      Ok(mut scheduler) => scheduler.describe(),
+      Err(_) => Ok(Vec::new()),
+    }
+  }
+
+  /// Insert a schedule in the application main loop, right before an existing
+  /// main schedule.
+  ///
+  /// Panics if the existing schedule is not part of the main loop or if the
+  /// new one already is.
+  ///
+  /// # Arguments
+  ///
+  /// * `before` - The [`ScheduleLabel`] of the existing main schedule.
+  /// * `label` - The [`ScheduleLabel`] of the schedule to insert.
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn add_schedule_before<B: ScheduleLabel + 'static, R: ScheduleLabel + 'static>(
+    &mut self,
+    before: B,
+    label: R,
+  ) -> &mut Self {
+    self.main_schedules.insert(&before, &label, 0);
+
+    self
+  }
+
+  /// Insert a schedule in the application main loop, right after an existing
+  /// main schedule.
+  ///
+  /// Panics if the existing schedule is not part of the main loop or if the
+  /// new one already is.
+  ///
+  /// # Arguments
+  ///
+  /// * `after` - The [`ScheduleLabel`] of the existing main schedule.
+  /// * `label` - The [`ScheduleLabel`] of the schedule to insert.
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn add_schedule_after<A: ScheduleLabel + 'static, R: ScheduleLabel + 'static>(
+    &mut self,
+    after: A,
+    label: R,
+  ) -> &mut Self {
+    self.main_schedules.insert(&after, &label, 1);
+
+    self
+  }
+
   /// Add a structure to the application's [`State`].
   ///
   /// # Arguments
@@ -155,7 +391,7 @@ impl App {
   ///
   /// * `->` - A mutable reference to the [`App`].
   #[inline]
-  pub fn add_state<R: 'static>(&mut self, structure: R) -> &mut Self {
+  pub fn add_state<R: Send + Sync + 'static>(&mut self, structure: R) -> &mut Self {
     if let Ok(mut state) = self.state.try_lock() {
       state.add(structure);
     }
@@ -163,6 +399,59 @@ impl App {
     self
   }
 
+  /// Add the default value of a structure to the application's [`State`],
+  /// unless it already contains one.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn init_state<R: Default + Send + Sync + 'static>(&mut self) -> &mut Self {
+    if let Ok(mut state) = self.state.try_lock() {
+      if !state.has::<R>() {
+        state.add(R::default());
+      }
+    }
+
+    self
+  }
+
+  /// Add an [`AppState`] of type `S` to the application's [`State`], along with
+  /// the [`NextState`] used to request transitions and the handlers running
+  /// the [`app_state::OnEnter`], [`app_state::OnExit`] and
+  /// [`app_state::OnUpdate`] schedules.
+  ///
+  /// # Arguments
+  ///
+  /// * `initial` - The state the application starts in.
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn add_app_state<S: States>(&mut self, initial: S) -> &mut Self {
+    self.add_state(AppState::new(initial));
+    self.add_state(NextState::<S>::default());
+
+    self.add_handler(StateTransition, app_state::apply_transitions::<S>());
+    self.add_handler(RunOnUpdate, app_state::run_on_update::<S>)
+  }
+
+  /// Add a structure that cannot be shared across threads to the application's
+  /// [`State`]. Handlers accessing it will always run on the main thread.
+  ///
+  /// # Arguments
+  ///
+  /// * `structure` - The object to add to the [`State`].
+  ///
+  /// * `->` - A mutable reference to the [`App`].
+  #[inline]
+  pub fn add_non_send_state<R: 'static>(&mut self, structure: R) -> &mut Self {
+    if let Ok(mut state) = self.state.try_lock() {
+      state.add_non_send(structure);
+    }
+
+    self
+  }
+
   /// Add a [`Module`] to the application.
   ///
   /// # Arguments
@@ -188,19 +477,47 @@ pub trait Module {
   fn configure(&self, app: &mut App);
 }
 
+/// A trait for deferred operations on the application's [`State`] that can be
+/// queued through [`Commands::queue`].
+///
+/// It is implemented for any function taking a mutable reference to a
+/// [`State`] and can be implemented by modules for their own operations.
+pub trait Command: Send + Sync + 'static {
+  /// Applies the command to a [`State`].
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] to apply the command to.
+  fn apply(self, state: &mut State);
+}
+
+impl<F: FnOnce(&mut State) + Send + Sync + 'static> Command for F {
+  fn apply(self, state: &mut State) {
+    self(state)
+  }
+}
+
+/// A queued operation, either a [`Command`] or an operation on handlers.
+enum Operation {
+  State(Box<dyn FnOnce(&mut State) + Send + Sync>),
+  Scheduler(Box<dyn FnOnce(&mut Scheduler) + Send + Sync>),
+}
+
 /// A structure to store application-specific commands that should be executed
 /// at the end of a schedule.
 ///
 /// Because schedules execution locks the application's state, we use a separate
 /// structure to store these in the form of commands. Once the schedule execution is
-/// completed, the commands are executed and reflected onto the application's state.
+/// completed, the commands are executed in the order they were issued and
+/// reflected onto the application's state.
 #[derive(Default)]
 pub struct Commands {
-  state: State,
+  operations: Vec<Operation>,
 }
 
 impl Commands {
-  /// Add a structure to the application's [`State`].
+  /// Add a structure to the application's [`State`], replacing any existing
+  /// structure of the same type.
   ///
   /// # Arguments
   ///
@@ -208,28 +525,303 @@ impl Commands {
   ///
   /// * `->` - A mutable reference to the [`Commands`].
   #[inline]
-  pub fn add_state<R: 'static>(&mut self, structure: R) -> &mut Self {
-    self.state.add(structure);
+  pub fn add_state<R: Send + Sync + 'static>(&mut self, structure: R) -> &mut Self {
+    self.queue(move |state: &mut State| state.add(structure))
+  }
 
-    self
+  /// Remove a structure from the application's [`State`], dropping it. Does
+  /// nothing if the state does not contain a structure of type `R`.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn remove_state<R: 'static>(&mut self) -> &mut Self {
+    self.queue(|state: &mut State| drop(state.remove::<R>()))
   }
 
-  /// Spawns a mesh instance with a specific material.
+  /// Update a structure of the application's [`State`], or add it if the
+  /// state does not contain a structure of type `R` yet.
+  ///
+  /// # Arguments
+  ///
+  /// * `insert` - A function creating the structure if it is missing.
+  /// * `update` - A function updating the structure if it exists.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn insert_or_update<R: Send + Sync + 'static>(
+    &mut self,
+    insert: impl FnOnce() -> R + Send + Sync + 'static,
+    update: impl FnOnce(&mut R) + Send + Sync + 'static,
+  ) -> &mut Self {
+    self.queue(move |state: &mut State| {
+      if !state.has::<R>() {
+        state.add(insert());
+      } else {
+        update(&mut state.get::<ResMut<R>>());
+      }
+    })
+  }
+
+  /// Spawns a mesh instance with a specific material, as an entity with the
+  /// [`crate::transform::Transform`] of the instance, its [`GlobalTransform`]
+  /// and its [`Visibility`].
   ///
   /// # Arguments
   ///
   /// * `instance` - The mesh instance to spawn.
+  ///
+  /// * `->` - A handle to the instance, resolved once the commands are applied.
   #[inline]
-  pub fn spawn<M: Material>(&mut self, instance: MeshInstance<M>) {
-    // Add a mesh to spawn container to state if there is none.
-    if !self.state.has::<MeshInstancesToSpawn<M>>() {
-      self.state.add(MeshInstancesToSpawn::<M>::default());
-    }
+  pub fn spawn<M: Material>(&mut self, instance: MeshInstance<M>) -> InstanceHandle<M> {
+    let handle = InstanceHandle::new();
+    let resolved = handle.clone();
+
+    self.queue(move |state: &mut State| {
+      let transform = instance.transform;
+      let entity = state.spawn((
+        transform,
+        GlobalTransform::from(transform),
+        Visibility::Visible,
+      ));
+      resolved.resolve(entity);
+
+      if !state.has::<MeshInstancesToSpawn<M>>() {
+        state.add(MeshInstancesToSpawn::<M>::default());
+      }
+
+      state
+        .get::<ResMut<MeshInstancesToSpawn<M>>>()
+        .push((entity, instance));
+    });
+
+    handle
+  }
+
+  /// Spawns a new entity with the components of a bundle. The entity is
+  /// reserved right away so that it can be referred to before the commands are
+  /// applied, its components are inserted once they are.
+  ///
+  /// # Arguments
+  ///
+  /// * `entities` - The [`Entities`] of the application's [`State`].
+  /// * `bundle` - The components of the entity.
+  ///
+  /// * `->` - The new [`Entity`].
+  #[inline]
+  pub fn spawn_entity(&mut self, entities: &Entities, bundle: impl Bundle) -> Entity {
+    let entity = entities.reserve();
+
+    self.insert(entity, bundle);
+
+    entity
+  }
+
+  /// Inserts the components of a bundle into an entity, replacing the
+  /// components of the same types it already has. Does nothing if the entity
+  /// was despawned.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to insert the components into.
+  /// * `bundle` - The components to insert.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn insert(&mut self, entity: Entity, bundle: impl Bundle) -> &mut Self {
+    self.queue(move |state: &mut State| {
+      state.insert(entity, bundle);
+    })
+  }
+
+  /// Removes the components of a bundle from an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to remove the components from.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn remove<B: Bundle>(&mut self, entity: Entity) -> &mut Self {
+    self.queue(move |state: &mut State| state.remove_components::<B>(entity))
+  }
+
+  /// Despawns an entity, dropping all of its components and detaching it from
+  /// the hierarchy. Does nothing if the entity was already despawned. See
+  /// [`State::despawn`].
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to despawn.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn despawn(&mut self, entity: Entity) -> &mut Self {
+    self.queue(move |state: &mut State| {
+      state.despawn(entity);
+    })
+  }
+
+  /// Queues a [`Command`] to be applied to the application's [`State`] after
+  /// the current schedule, in the order it was issued relative to other
+  /// commands.
+  ///
+  /// # Arguments
+  ///
+  /// * `command` - The [`Command`] to apply.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn queue(&mut self, command: impl Command) -> &mut Self {
+    self
+      .operations
+      .push(Operation::State(Box::new(move |state: &mut State| {
+        command.apply(state)
+      })));
+
+    self
+  }
+
+  /// Adds a [`Handler`](crate::state::Handler) to a specific schedule once the
+  /// current schedule has completed.
+  ///
+  /// Applying the command panics if the handler requests conflicting accesses
+  /// to a structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The [`ScheduleLabel`] to add the handler to.
+  /// * `handler` - The handler to add to the schedule.
+  ///
+  /// * `->` - The identifier the handler will have once added.
+  pub fn add_handler<R: ScheduleLabel + 'static, I>(
+    &mut self,
+    label: R,
+    handler: impl IntoHandlerConfig<I>,
+  ) -> HandlerId {
+    let key = label.key();
+    let handler = handler.into_config();
+    let id = handler.id();
+
+    // Handlers can be sent to other threads but not shared between them.
+    let handler = Mutex::new(handler);
+
+    self.operations.push(Operation::Scheduler(Box::new(
+      move |scheduler: &mut Scheduler| {
+        scheduler.add_handler_by_key(key, handler.into_inner().unwrap());
+      },
+    )));
+
+    id
+  }
+
+  /// Removes a handler from its schedule once the current schedule has
+  /// completed.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler to remove.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  pub fn remove_handler(&mut self, id: HandlerId) -> &mut Self {
+    self.operations.push(Operation::Scheduler(Box::new(
+      move |scheduler: &mut Scheduler| scheduler.remove_handler(id),
+    )));
+
+    self
+  }
+
+  /// Enables a disabled handler once the current schedule has completed.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler to enable.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  pub fn enable_handler(&mut self, id: HandlerId) -> &mut Self {
+    self.operations.push(Operation::Scheduler(Box::new(
+      move |scheduler: &mut Scheduler| scheduler.set_handler_enabled(id, true),
+    )));
+
+    self
+  }
+
+  /// Disables a handler once the current schedule has completed, skipping it
+  /// until it is enabled again.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler to disable.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  pub fn disable_handler(&mut self, id: HandlerId) -> &mut Self {
+    self.operations.push(Operation::Scheduler(Box::new(
+      move |scheduler: &mut Scheduler| scheduler.set_handler_enabled(id, false),
+    )));
 
-    // Push the instance into the state.
     self
-      .state
-      .get::<ResMut<MeshInstancesToSpawn<M>>>()
-      .push(instance);
+  }
+
+  /// Takes the commands queued in the [`Commands`] of a [`State`], if it has
+  /// any, and applies them in order.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] holding the commands.
+  /// * `scheduler` - The [`Scheduler`] to apply operations on handlers to.
+  pub(crate) fn apply_queued(state: &mut State, scheduler: &mut Scheduler) {
+    let mut commands = match state.get::<Option<ResMut<Commands>>>() {
+      Some(mut commands) => std::mem::take(commands.bypass_change_detection()),
+      None => return,
+    };
+
+    commands.apply_all(state, scheduler);
+  }
+
+  /// Applies all queued commands to a [`State`] in order.
+  ///
+  /// This only needs to be called when running a [`Scheduler`] manually and
+  /// when no operations on handlers were queued. Panics before applying any
+  /// command otherwise, as they could not be applied without a [`Scheduler`];
+  /// use [`Commands::apply_all`] instead.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] to apply the commands to.
+  pub fn apply(self, state: &mut State) {
+    assert!(
+      self
+        .operations
+        .iter()
+        .all(|operation| matches!(operation, Operation::State(_))),
+      "Operations on handlers cannot be applied without a scheduler, use \
+       `Commands::apply_all` instead."
+    );
+
+    for operation in self.operations {
+      if let Operation::State(operation) = operation {
+        operation(state);
+      }
+    }
+  }
+
+  /// Applies all queued commands to a [`State`] and all queued operations on
+  /// handlers to a [`Scheduler`], in the order they were issued.
+  ///
+  /// This is done by the [`App`] after each schedule and only needs to be
+  /// called when running a [`Scheduler`] manually.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] to apply the commands to.
+  /// * `scheduler` - The [`Scheduler`] to apply the operations on handlers to.
+  pub fn apply_all(&mut self, state: &mut State, scheduler: &mut Scheduler) {
+    for operation in self.operations.drain(..) {
+      match operation {
+        Operation::State(operation) => operation(state),
+        Operation::Scheduler(operation) => operation(scheduler),
+      }
+    }
   }
 }
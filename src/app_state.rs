@@ -0,0 +1,168 @@
+use crate::{
+  app::Commands,
+  state::{Res, ResMut, ScheduleKey, ScheduleLabel, Scheduler, State},
+};
+
+use std::{fmt::Debug, hash::Hash};
+
+/// A trait for the types that can be used as application states, usually
+/// field-less enums listing the stages of the application such as its menus,
+/// loading screens and gameplay.
+///
+/// It is implemented for any type meeting its requirements.
+pub trait States: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<S: Clone + Eq + Hash + Debug + Send + Sync + 'static> States for S {}
+
+/// A structure holding the current application state of type `S`.
+///
+/// It is added through [`crate::app::App::add_app_state`] and should only be
+/// changed through [`NextState`] so that transitions run their schedules.
+pub struct AppState<S: States>(S);
+
+impl<S: States> AppState<S> {
+  /// Creates a new [`AppState`].
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The initial application state.
+  ///
+  /// * `->` - A new [`AppState`].
+  pub fn new(state: S) -> Self {
+    Self(state)
+  }
+
+  /// Returns the current application state.
+  pub fn get(&self) -> &S {
+    &self.0
+  }
+}
+
+/// A structure requesting a transition of the [`AppState`] of type `S`.
+///
+/// The transition is applied between the [`crate::app::PreUpdate`] and the
+/// fixed update schedules, running the [`OnExit`] handlers of the current state
+/// and the [`OnEnter`] handlers of the next one.
+pub struct NextState<S: States>(Option<S>);
+
+impl<S: States> Default for NextState<S> {
+  fn default() -> Self {
+    Self(None)
+  }
+}
+
+impl<S: States> NextState<S> {
+  /// Requests a transition to the given state. Only the last request made
+  /// before the transition is applied is taken into account.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The state to transition to.
+  pub fn set(&mut self, state: S) {
+    self.0 = Some(state);
+  }
+
+  /// Returns the pending transition, if any.
+  pub fn pending(&self) -> Option<&S> {
+    self.0.as_ref()
+  }
+}
+
+/// A schedule label that represents the schedule running when the application
+/// enters a state.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OnEnter<S: States>(pub S);
+
+/// A schedule label that represents the schedule running when the application
+/// exits a state.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OnExit<S: States>(pub S);
+
+/// A schedule label that represents the schedule running right after the
+/// [`crate::app::Update`] schedule while the application is in a state.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OnUpdate<S: States>(pub S);
+
+// The state labels are named after the state they carry.
+impl<S: States> ScheduleLabel for OnEnter<S> {
+  fn key(&self) -> ScheduleKey {
+    ScheduleKey::new(self.clone()).with_name(format!("OnEnter({:?})", self.0))
+  }
+}
+
+impl<S: States> ScheduleLabel for OnExit<S> {
+  fn key(&self) -> ScheduleKey {
+    ScheduleKey::new(self.clone()).with_name(format!("OnExit({:?})", self.0))
+  }
+}
+
+impl<S: States> ScheduleLabel for OnUpdate<S> {
+  fn key(&self) -> ScheduleKey {
+    ScheduleKey::new(self.clone()).with_name(format!("OnUpdate({:?})", self.0))
+  }
+}
+
+/// Returns the exclusive handler applying the transitions requested through
+/// [`NextState`] for states of type `S`.
+///
+/// The first run enters the initial state. Later runs exit the current state
+/// and enter the requested one, unless both are equal. The commands queued by
+/// the [`OnExit`] and [`OnEnter`] handlers are applied right after they run.
+///
+/// It is registered by [`crate::app::App::add_app_state`] and only needs to be
+/// added manually when driving a [`Scheduler`] without an application.
+pub fn apply_transitions<S: States>() -> impl FnMut(&mut State, &mut Scheduler) {
+  let mut entered = false;
+
+  move |state: &mut State, scheduler: &mut Scheduler| {
+    let Some(current) = state
+      .get::<Option<Res<AppState<S>>>>()
+      .map(|current| current.get().clone())
+    else {
+      return;
+    };
+
+    if !entered {
+      entered = true;
+
+      scheduler.run(OnEnter(current.clone()), state);
+      Commands::apply_queued(state, scheduler);
+    }
+
+    let next = state
+      .get::<Option<ResMut<NextState<S>>>>()
+      .and_then(|mut next| next.bypass_change_detection().0.take());
+
+    let Some(next) = next.filter(|next| *next != current) else {
+      return;
+    };
+
+    scheduler.run(OnExit(current), state);
+    Commands::apply_queued(state, scheduler);
+
+    state.get::<ResMut<AppState<S>>>().0 = next.clone();
+
+    scheduler.run(OnEnter(next), state);
+    Commands::apply_queued(state, scheduler);
+  }
+}
+
+/// Runs the [`OnUpdate`] schedule for states of type `S`.
+///
+/// It is registered by [`crate::app::App::add_app_state`] and only needs to be
+/// added manually when driving a [`Scheduler`] without an application.
+///
+/// # Arguments
+///
+/// * `state` - The application [`State`].
+/// * `scheduler` - The application [`Scheduler`].
+pub fn run_on_update<S: States>(state: &mut State, scheduler: &mut Scheduler) {
+  let Some(current) = state
+    .get::<Option<Res<AppState<S>>>>()
+    .map(|current| current.get().clone())
+  else {
+    return;
+  };
+
+  scheduler.run(OnUpdate(current), state);
+}
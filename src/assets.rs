@@ -27,21 +27,23 @@ impl Assets {
   /// Load an asset from a file path. If the asset already exists in memory, it
   /// will not be loaded.
   ///
-  /// TODO: Implement error handling for file reading failures.
-  ///
   /// # Arguments
   ///
   /// * `path` - The file path of the asset to load.
+  ///
+  /// * `->` - An error if the file could not be read.
   #[inline]
-  pub fn load(&mut self, path: &'static str) {
+  pub fn load(&mut self, path: &'static str) -> std::io::Result<()> {
     if !self.storage.contains_key(path) {
       self.storage.insert(
         path,
         Asset {
-          data: std::fs::read(path).unwrap(),
+          data: std::fs::read(path)?,
         },
       );
     }
+
+    Ok(())
   }
 
   /// Return a reference to the asset with the given path. If the asset does not
@@ -51,10 +53,12 @@ impl Assets {
   ///
   /// * `path` - The path of the asset to retrieve.
   ///
-  /// * `->` - A reference to the asset with the given path.
-  pub fn get(&mut self, path: &'static str) -> &Asset {
-    self.load(path);
-    self.storage.get(path).unwrap()
+  /// * `->` - A reference to the asset with the given path or an error if it
+  ///   could not be loaded.
+  pub fn get(&mut self, path: &'static str) -> std::io::Result<&Asset> {
+    self.load(path)?;
+
+    Ok(&self.storage[path])
   }
 
   /// Unload the asset with the given path from memory.
@@ -0,0 +1,178 @@
+use crate::{
+  app_state::{AppState, States},
+  state::{Access, Handler, IntoHandler, Res, Structures},
+};
+
+/// A trait for handler functions returning a [`bool`] that can be used as run
+/// conditions through [`crate::state::IntoHandlerConfig::run_if`].
+///
+/// It is implemented for any such function and provides combinators to build
+/// more complex conditions out of simpler ones.
+pub trait Condition<Input>: IntoHandler<Input, bool> + Sized {
+  /// Combines two conditions into one that is met when both are met. The
+  /// second condition is not evaluated if the first one is not met.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The condition to combine with.
+  ///
+  /// * `->` - A new condition combining both conditions.
+  fn and<I, C: Condition<I>>(self, other: C) -> AndCondition<Self::Handler, C::Handler> {
+    AndCondition {
+      a: self.into_handler(),
+      b: other.into_handler(),
+    }
+  }
+
+  /// Combines two conditions into one that is met when either is met. The
+  /// second condition is not evaluated if the first one is met.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The condition to combine with.
+  ///
+  /// * `->` - A new condition combining both conditions.
+  fn or<I, C: Condition<I>>(self, other: C) -> OrCondition<Self::Handler, C::Handler> {
+    OrCondition {
+      a: self.into_handler(),
+      b: other.into_handler(),
+    }
+  }
+
+  /// Inverts the condition.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - A new condition that is met when this one is not.
+  fn not(self) -> NotCondition<Self::Handler> {
+    NotCondition {
+      inner: self.into_handler(),
+    }
+  }
+}
+
+impl<I, C: IntoHandler<I, bool>> Condition<I> for C {}
+
+/// A condition that is met when both of its inner conditions are met.
+pub struct AndCondition<A, B> {
+  a: A,
+  b: B,
+}
+
+impl<A: Handler<bool>, B: Handler<bool>> Handler<bool> for AndCondition<A, B> {
+  fn run(&mut self, structures: &Structures, tick: u64) -> bool {
+    self.a.run(structures, tick) && self.b.run(structures, tick)
+  }
+
+  fn access(&self, access: &mut Access) {
+    access.extend(&condition_access(&self.a));
+    access.extend(&condition_access(&self.b));
+  }
+}
+
+impl<A: Handler<bool>, B: Handler<bool>> IntoHandler<Self, bool> for AndCondition<A, B> {
+  type Handler = Self;
+
+  fn into_handler(self) -> Self::Handler {
+    self
+  }
+}
+
+/// A condition that is met when either of its inner conditions is met.
+pub struct OrCondition<A, B> {
+  a: A,
+  b: B,
+}
+
+impl<A: Handler<bool>, B: Handler<bool>> Handler<bool> for OrCondition<A, B> {
+  fn run(&mut self, structures: &Structures, tick: u64) -> bool {
+    self.a.run(structures, tick) || self.b.run(structures, tick)
+  }
+
+  fn access(&self, access: &mut Access) {
+    access.extend(&condition_access(&self.a));
+    access.extend(&condition_access(&self.b));
+  }
+}
+
+impl<A: Handler<bool>, B: Handler<bool>> IntoHandler<Self, bool> for OrCondition<A, B> {
+  type Handler = Self;
+
+  fn into_handler(self) -> Self::Handler {
+    self
+  }
+}
+
+/// A condition that is met when its inner condition is not.
+pub struct NotCondition<C> {
+  inner: C,
+}
+
+impl<C: Handler<bool>> Handler<bool> for NotCondition<C> {
+  fn run(&mut self, structures: &Structures, tick: u64) -> bool {
+    !self.inner.run(structures, tick)
+  }
+
+  fn access(&self, access: &mut Access) {
+    self.inner.access(access);
+  }
+}
+
+impl<C: Handler<bool>> IntoHandler<Self, bool> for NotCondition<C> {
+  type Handler = Self;
+
+  fn into_handler(self) -> Self::Handler {
+    self
+  }
+}
+
+/// Returns the accesses of a single condition. Combined conditions run one
+/// after the other so their accesses are merged without conflicting.
+fn condition_access(condition: &impl Handler<bool>) -> Access {
+  let mut access = Access::default();
+  condition.access(&mut access);
+
+  access
+}
+
+/// Returns a condition that is met when a structure of type `T` exists in the
+/// state.
+pub fn resource_exists<T: 'static>() -> impl FnMut(Option<Res<T>>) -> bool {
+  |structure: Option<Res<T>>| structure.is_some()
+}
+
+/// Returns a condition that is met when a structure of type `T` exists in the
+/// state and is equal to the given value.
+///
+/// # Arguments
+///
+/// * `value` - The value to compare the structure with.
+pub fn resource_equals<T: PartialEq + Send + 'static>(
+  value: T,
+) -> impl FnMut(Option<Res<T>>) -> bool {
+  move |structure: Option<Res<T>>| structure.is_some_and(|structure| *structure == value)
+}
+
+/// Returns a condition that is met when a structure of type `T` exists in the
+/// state and was added or mutably dereferenced since the last time the
+/// condition was evaluated. The first evaluation on an existing structure is
+/// always met.
+pub fn resource_changed<T: 'static>() -> impl FnMut(Option<Res<T>>) -> bool {
+  |structure: Option<Res<T>>| structure.is_some_and(|structure| structure.is_changed())
+}
+
+/// Returns a condition that is met when a structure of type `T` was added to
+/// the state since the last time the condition was evaluated.
+pub fn resource_added<T: 'static>() -> impl FnMut(Option<Res<T>>) -> bool {
+  |structure: Option<Res<T>>| structure.is_some_and(|structure| structure.is_added())
+}
+
+/// Returns a condition that is met when the [`AppState`] of type `S` exists in
+/// the state and is equal to the given value.
+///
+/// # Arguments
+///
+/// * `value` - The application state to compare with.
+pub fn in_state<S: States>(value: S) -> impl FnMut(Option<Res<AppState<S>>>) -> bool {
+  move |state: Option<Res<AppState<S>>>| state.is_some_and(|state| *state.get() == value)
+}
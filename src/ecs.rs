@@ -0,0 +1,1038 @@
+pub use charbs_macros::Component;
+
+use crate::state::{Access, HandlerParam, Res, ResMut, State, Structures, Ticks};
+
+use std::{
+  any::TypeId,
+  collections::HashMap,
+  marker::PhantomData,
+  sync::{Mutex, MutexGuard},
+};
+
+/// A generational identifier of an entity.
+///
+/// Indices of despawned entities are reused by later entities with a higher
+/// generation, so that identifiers of despawned entities never refer to new
+/// ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+  index: u32,
+  generation: u32,
+}
+
+impl Entity {
+  /// Returns the index of the entity, shared with despawned entities.
+  pub fn index(&self) -> u32 {
+    self.index
+  }
+
+  /// Returns the generation of the entity, telling it apart from despawned
+  /// entities with the same index.
+  pub fn generation(&self) -> u32 {
+    self.generation
+  }
+}
+
+/// A trait for the types that can be attached to entities.
+///
+/// There is a proc macro that provides automatic impl of the `Component` trait
+/// for any struct or enum.
+pub trait Component: Send + Sync + 'static {}
+
+/// The allocator of the entity identifiers.
+#[derive(Default)]
+struct Allocator {
+  generations: Vec<u32>,
+  alive: Vec<bool>,
+  free: Vec<u32>,
+  len: usize,
+}
+
+/// A type alias for a function removing a component from an entity.
+type RemoveFn = fn(&mut State, Entity);
+
+/// A structure keeping track of the entities of a [`State`].
+///
+/// Components are stored in one [`Components`] structure per type, alongside
+/// the other structures of the state, so that handlers accessing different
+/// components can run concurrently.
+#[derive(Default)]
+pub struct Entities {
+  allocator: Mutex<Allocator>,
+  components: HashMap<TypeId, RemoveFn>,
+}
+
+impl Entities {
+  /// Reserves a new entity without any component. Components can then be
+  /// inserted through [`crate::app::Commands::insert`], which allows handlers
+  /// to know the identifier of an entity before it is populated, as
+  /// [`crate::app::Commands::spawn_entity`] does.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The new [`Entity`].
+  pub fn reserve(&self) -> Entity {
+    let mut allocator = self.allocator();
+    allocator.len += 1;
+
+    if let Some(index) = allocator.free.pop() {
+      allocator.alive[index as usize] = true;
+
+      return Entity {
+        index,
+        generation: allocator.generations[index as usize],
+      };
+    }
+
+    allocator.generations.push(0);
+    allocator.alive.push(true);
+
+    Entity {
+      index: allocator.generations.len() as u32 - 1,
+      generation: 0,
+    }
+  }
+
+  /// Returns whether an entity exists and has not been despawned.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  pub fn contains(&self, entity: Entity) -> bool {
+    let allocator = self.allocator();
+    let index = entity.index as usize;
+
+    allocator.alive.get(index).copied().unwrap_or(false)
+      && allocator.generations[index] == entity.generation
+  }
+
+  /// Returns the number of entities.
+  pub fn len(&self) -> usize {
+    self.allocator().len
+  }
+
+  /// Returns whether there is no entity.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Frees an entity so that its index can be reused.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to free.
+  ///
+  /// * `->` - Whether the entity existed.
+  fn free(&mut self, entity: Entity) -> bool {
+    if !self.contains(entity) {
+      return false;
+    }
+
+    let allocator = self.allocator.get_mut().unwrap_or_else(|e| e.into_inner());
+    let index = entity.index as usize;
+
+    allocator.alive[index] = false;
+    allocator.generations[index] += 1;
+    allocator.free.push(entity.index);
+    allocator.len -= 1;
+
+    true
+  }
+
+  /// Locks the allocator, recovering from a poisoned lock as it cannot be left
+  /// in an inconsistent state.
+  fn allocator(&self) -> MutexGuard<'_, Allocator> {
+    self.allocator.lock().unwrap_or_else(|e| e.into_inner())
+  }
+}
+
+/// A structure storing the components of type `C` of every entity, as a sparse
+/// set. Components are packed in a dense array, which makes iterating them
+/// fast, while looking them up by entity takes constant time.
+pub struct Components<C: Component> {
+  sparse: Vec<Option<usize>>,
+  entities: Vec<Entity>,
+  values: Vec<C>,
+}
+
+impl<C: Component> Default for Components<C> {
+  fn default() -> Self {
+    Self {
+      sparse: Vec::new(),
+      entities: Vec::new(),
+      values: Vec::new(),
+    }
+  }
+}
+
+impl<C: Component> Components<C> {
+  /// Returns the position of the component of an entity in the dense array.
+  fn position(&self, entity: Entity) -> Option<usize> {
+    self
+      .sparse
+      .get(entity.index as usize)
+      .copied()
+      .flatten()
+      .filter(|&position| self.entities[position] == entity)
+  }
+
+  /// Returns the component of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity owning the component.
+  pub fn get(&self, entity: Entity) -> Option<&C> {
+    self.position(entity).map(|position| &self.values[position])
+  }
+
+  /// Returns a mutable reference to the component of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity owning the component.
+  pub fn get_mut(&mut self, entity: Entity) -> Option<&mut C> {
+    self
+      .position(entity)
+      .map(|position| &mut self.values[position])
+  }
+
+  /// Returns whether an entity has a component of type `C`.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  pub fn contains(&self, entity: Entity) -> bool {
+    self.position(entity).is_some()
+  }
+
+  /// Returns the number of components.
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  /// Returns whether there is no component.
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  /// Returns an iterator over the components along with their entity.
+  pub fn iter(&self) -> impl Iterator<Item = (Entity, &C)> {
+    self.entities.iter().copied().zip(self.values.iter())
+  }
+
+  /// Returns an iterator over mutable references to the components along with
+  /// their entity.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut C)> {
+    self.entities.iter().copied().zip(self.values.iter_mut())
+  }
+
+  /// Inserts the component of an entity, replacing any existing one.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity owning the component.
+  /// * `value` - The component.
+  ///
+  /// * `->` - The replaced component, if any.
+  fn insert(&mut self, entity: Entity, value: C) -> Option<C> {
+    if let Some(position) = self.position(entity) {
+      return Some(std::mem::replace(&mut self.values[position], value));
+    }
+
+    let index = entity.index as usize;
+
+    if self.sparse.len() <= index {
+      self.sparse.resize(index + 1, None);
+    }
+
+    self.sparse[index] = Some(self.values.len());
+    self.entities.push(entity);
+    self.values.push(value);
+
+    None
+  }
+
+  /// Removes the component of an entity, moving the last component in its
+  /// place.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity owning the component.
+  ///
+  /// * `->` - The removed component, if any.
+  fn remove(&mut self, entity: Entity) -> Option<C> {
+    let position = self.position(entity)?;
+
+    self.sparse[entity.index as usize] = None;
+    self.entities.swap_remove(position);
+
+    if let Some(moved) = self.entities.get(position) {
+      self.sparse[moved.index as usize] = Some(position);
+    }
+
+    Some(self.values.swap_remove(position))
+  }
+}
+
+/// A trait for groups of components that can be inserted into or removed from
+/// an entity at once.
+///
+/// It is implemented for every [`Component`] and for tuples of bundles.
+pub trait Bundle: Send + Sync + 'static {
+  /// Inserts the components of the bundle into an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] storing the components.
+  /// * `entity` - The entity to insert the components into.
+  fn insert(self, state: &mut State, entity: Entity);
+
+  /// Removes the components of the bundle from an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - The [`State`] storing the components.
+  /// * `entity` - The entity to remove the components from.
+  fn remove(state: &mut State, entity: Entity);
+}
+
+impl<C: Component> Bundle for C {
+  fn insert(self, state: &mut State, entity: Entity) {
+    // Create the storage of the component the first time it is used.
+    if !state.has::<Components<C>>() {
+      state.add(Components::<C>::default());
+      state
+        .get::<ResMut<Entities>>()
+        .components
+        .insert(TypeId::of::<C>(), remove_component::<C>);
+    }
+
+    state.get::<ResMut<Components<C>>>().insert(entity, self);
+  }
+
+  fn remove(state: &mut State, entity: Entity) {
+    remove_component::<C>(state, entity);
+  }
+}
+
+/// Removes the component of type `C` of an entity, if any.
+fn remove_component<C: Component>(state: &mut State, entity: Entity) {
+  if let Some(mut components) = state.get::<Option<ResMut<Components<C>>>>() {
+    components.remove(entity);
+  }
+}
+
+macro_rules! impl_bundle {
+  ($($bundle:ident),*) => {
+    #[allow(non_snake_case)]
+    impl<$($bundle: Bundle),*> Bundle for ($($bundle,)*) {
+      fn insert(self, state: &mut State, entity: Entity) {
+        let ($($bundle,)*) = self;
+        $($bundle.insert(state, entity);)*
+      }
+
+      fn remove(state: &mut State, entity: Entity) {
+        $($bundle::remove(state, entity);)*
+      }
+    }
+  };
+}
+
+// Allow for 8 components per bundle
+impl_bundle!(B1);
+impl_bundle!(B1, B2);
+impl_bundle!(B1, B2, B3);
+impl_bundle!(B1, B2, B3, B4);
+impl_bundle!(B1, B2, B3, B4, B5);
+impl_bundle!(B1, B2, B3, B4, B5, B6);
+impl_bundle!(B1, B2, B3, B4, B5, B6, B7);
+impl_bundle!(B1, B2, B3, B4, B5, B6, B7, B8);
+
+impl State {
+  /// Spawns a new entity with the components of a bundle.
+  ///
+  /// # Arguments
+  ///
+  /// * `bundle` - The components of the entity.
+  ///
+  /// * `->` - The new [`Entity`].
+  pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+    if !self.has::<Entities>() {
+      self.add(Entities::default());
+    }
+
+    let entity = self.get::<ResMut<Entities>>().reserve();
+    bundle.insert(self, entity);
+
+    entity
+  }
+
+  /// Inserts the components of a bundle into an existing entity, replacing
+  /// the components of the same types it already has.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to insert the components into.
+  /// * `bundle` - The components to insert.
+  ///
+  /// * `->` - Whether the entity exists.
+  pub fn insert(&mut self, entity: Entity, bundle: impl Bundle) -> bool {
+    if !self.contains(entity) {
+      return false;
+    }
+
+    bundle.insert(self, entity);
+
+    true
+  }
+
+  /// Removes the components of a bundle from an entity. Components the entity
+  /// does not have are ignored.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to remove the components from.
+  pub fn remove_components<B: Bundle>(&mut self, entity: Entity) {
+    B::remove(self, entity);
+  }
+
+  /// Despawns an entity, dropping all of its components. The entity is
+  /// detached from its parent and its children from it, keeping their world
+  /// position. See [`State::despawn_recursive`] to despawn them as well.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to despawn.
+  ///
+  /// * `->` - Whether the entity existed.
+  pub fn despawn(&mut self, entity: Entity) -> bool {
+    self.unlink(entity);
+
+    let components: Vec<RemoveFn> = match self.get::<Option<ResMut<Entities>>>() {
+      Some(mut entities) => match entities.free(entity) {
+        true => entities.components.values().copied().collect(),
+        false => return false,
+      },
+      None => return false,
+    };
+
+    for remove in components {
+      remove(self, entity);
+    }
+
+    true
+  }
+
+  /// Returns whether an entity exists and has not been despawned.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  pub fn contains(&self, entity: Entity) -> bool {
+    self
+      .get::<Option<Res<Entities>>>()
+      .is_some_and(|entities| entities.contains(entity))
+  }
+}
+
+/// A trait for the data a [`Query`] retrieves for each entity.
+///
+/// It is implemented for [`Entity`], for `&C` and `&mut C` where `C` is a
+/// [`Component`], and for tuples of query data.
+pub trait QueryData {
+  /// The borrowed component storages the data is retrieved from.
+  type Fetch<'w>;
+
+  /// The data retrieved for an entity.
+  type Item<'f>;
+
+  /// The read-only data retrieved for an entity.
+  type ReadItem<'f>;
+
+  /// Borrows the component storages of the data.
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the injectable structures instance.
+  /// * `ticks` - The change ticks of the handler run.
+  ///
+  /// * `->` - The borrowed storages, or [`None`] if a storage does not exist,
+  ///   in which case no entity has the data.
+  fn fetch<'w>(structures: &'w Structures, ticks: Ticks) -> Option<Self::Fetch<'w>>;
+
+  /// Returns the entities of the smallest storage of the data, which bound the
+  /// entities having the whole data.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  ///
+  /// * `->` - The entities, or [`None`] if the data has no storage.
+  fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]>;
+
+  /// Returns the read-only data of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  /// * `entity` - The entity owning the data.
+  fn read<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::ReadItem<'f>>;
+
+  /// Returns the data of an entity.
+  ///
+  /// # Safety
+  ///
+  /// The caller must not hold any other item of the same entity retrieved from
+  /// the same storages, and must have called [`QueryData::set_changed`] since
+  /// the storages were last read.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  /// * `entity` - The entity owning the data.
+  unsafe fn get<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity)
+  -> Option<Self::Item<'f>>;
+
+  /// Marks the storages the data mutably accesses as changed.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  fn set_changed(fetch: &mut Self::Fetch<'_>);
+
+  /// Declares the storages the data reads and writes.
+  ///
+  /// # Arguments
+  ///
+  /// * `access` - The [`Access`] of the handler the query belongs to.
+  fn access(access: &mut Access);
+}
+
+impl QueryData for Entity {
+  type Fetch<'w> = ();
+
+  type Item<'f> = Entity;
+
+  type ReadItem<'f> = Entity;
+
+  fn fetch<'w>(_: &'w Structures, _: Ticks) -> Option<Self::Fetch<'w>> {
+    Some(())
+  }
+
+  fn entities<'f>(_: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+    None
+  }
+
+  fn read<'f>(_: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::ReadItem<'f>> {
+    Some(entity)
+  }
+
+  unsafe fn get<'f>(_: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'f>> {
+    Some(entity)
+  }
+
+  fn set_changed(_: &mut Self::Fetch<'_>) {}
+
+  fn access(_: &mut Access) {}
+}
+
+impl<C: Component> QueryData for &C {
+  type Fetch<'w> = Res<'w, Components<C>>;
+
+  type Item<'f> = &'f C;
+
+  type ReadItem<'f> = &'f C;
+
+  fn fetch<'w>(structures: &'w Structures, ticks: Ticks) -> Option<Self::Fetch<'w>> {
+    structures
+      .get(&TypeId::of::<Components<C>>())
+      .map(|structure| Res::new(structure, ticks))
+  }
+
+  fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+    Some(&fetch.entities)
+  }
+
+  fn read<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::ReadItem<'f>> {
+    fetch.get(entity)
+  }
+
+  unsafe fn get<'f>(
+    fetch: &'f Self::Fetch<'_>,
+    entity: Entity,
+  ) -> Option<Self::Item<'f>> {
+    fetch.get(entity)
+  }
+
+  fn set_changed(_: &mut Self::Fetch<'_>) {}
+
+  fn access(access: &mut Access) {
+    access.add_read::<Components<C>>();
+  }
+}
+
+/// The mutably borrowed storage of the components of type `C`, from which a
+/// [`Query`] hands out mutable references to the components of different
+/// entities at the same time.
+pub struct WriteFetch<'w, C: Component> {
+  guard: ResMut<'w, Components<C>>,
+  components: *mut Components<C>,
+}
+
+impl<C: Component> QueryData for &mut C {
+  type Fetch<'w> = WriteFetch<'w, C>;
+
+  type Item<'f> = &'f mut C;
+
+  type ReadItem<'f> = &'f C;
+
+  fn fetch<'w>(structures: &'w Structures, ticks: Ticks) -> Option<Self::Fetch<'w>> {
+    let mut guard = ResMut::<Components<C>>::new(
+      structures.get(&TypeId::of::<Components<C>>())?,
+      ticks,
+    );
+    let components: *mut Components<C> = guard.bypass_change_detection();
+
+    Some(WriteFetch { guard, components })
+  }
+
+  fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+    // SAFETY: The storage is locked by the guard and its entities are never
+    // mutably borrowed, only its components are.
+    Some(unsafe { &(*fetch.components).entities })
+  }
+
+  fn read<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::ReadItem<'f>> {
+    // SAFETY: Mutable items borrow the fetch mutably, so none are alive here.
+    unsafe { (*fetch.components).get(entity) }
+  }
+
+  unsafe fn get<'f>(
+    fetch: &'f Self::Fetch<'_>,
+    entity: Entity,
+  ) -> Option<Self::Item<'f>> {
+    // SAFETY: The storage is locked by the guard and the caller guarantees that
+    // no other reference to the component of this entity is alive.
+    unsafe {
+      let components = fetch.components;
+      let position = (*components).position(entity)?;
+
+      Some(&mut *(*components).values.as_mut_ptr().add(position))
+    }
+  }
+
+  fn set_changed(fetch: &mut Self::Fetch<'_>) {
+    fetch.components = &mut *fetch.guard;
+  }
+
+  fn access(access: &mut Access) {
+    access.add_write::<Components<C>>();
+  }
+}
+
+/// A trait for the filters restricting the entities of a [`Query`].
+///
+/// It is implemented for [`With`], [`Without`] and tuples of filters, which
+/// match the entities matching all of their filters.
+pub trait QueryFilter {
+  /// The borrowed component storages the filter checks.
+  type Fetch<'w>;
+
+  /// Borrows the component storages of the filter.
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the injectable structures instance.
+  /// * `ticks` - The change ticks of the handler run.
+  /// * `data` - The [`Access`] of the data of the query, whose storages are
+  ///   already borrowed and must not be borrowed again.
+  ///
+  /// * `->` - The borrowed storages.
+  fn fetch<'w>(
+    structures: &'w Structures,
+    ticks: Ticks,
+    data: &Access,
+  ) -> Self::Fetch<'w>;
+
+  /// Returns whether an entity matches the filter.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  /// * `entity` - The entity to check.
+  fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool;
+
+  /// Returns the entities of the smallest storage of the components the filter
+  /// requires, which bound the entities matching the filter.
+  ///
+  /// # Arguments
+  ///
+  /// * `fetch` - The borrowed storages.
+  ///
+  /// * `->` - The entities, or [`None`] if the filter requires no borrowed
+  ///   storage.
+  fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]>;
+
+  /// Declares the storages the filter reads, apart from those the data of the
+  /// query already accesses.
+  ///
+  /// # Arguments
+  ///
+  /// * `access` - The [`Access`] of the handler the query belongs to.
+  /// * `data` - The [`Access`] of the data of the query.
+  fn access(access: &mut Access, data: &Access);
+}
+
+/// The storage checked by a [`With`] or [`Without`] filter.
+pub enum FilterFetch<'w, C: Component> {
+  /// The storage is borrowed by the data of the query, so every entity the
+  /// query yields has the component.
+  Data,
+  /// The storage, or [`None`] if no entity has the component.
+  Borrowed(Option<Res<'w, Components<C>>>),
+}
+
+impl<'w, C: Component> FilterFetch<'w, C> {
+  /// Borrows the storage of the components of type `C`, unless the data of the
+  /// query already does.
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the injectable structures instance.
+  /// * `ticks` - The change ticks of the handler run.
+  /// * `data` - The [`Access`] of the data of the query.
+  fn new(structures: &'w Structures, ticks: Ticks, data: &Access) -> Self {
+    let key = TypeId::of::<Components<C>>();
+
+    if data.types().any(|&accessed| accessed == key) {
+      return FilterFetch::Data;
+    }
+
+    FilterFetch::Borrowed(
+      structures
+        .get(&key)
+        .map(|structure| Res::new(structure, ticks)),
+    )
+  }
+
+  /// Returns whether an entity has the component.
+  fn contains(&self, entity: Entity) -> bool {
+    match self {
+      FilterFetch::Data => true,
+      FilterFetch::Borrowed(components) => components
+        .as_ref()
+        .is_some_and(|components| components.contains(entity)),
+    }
+  }
+
+  /// Declares a read of the storage, unless the data of the query accesses it.
+  fn access(access: &mut Access, data: &Access) {
+    let key = TypeId::of::<Components<C>>();
+
+    if !data.types().any(|&accessed| accessed == key) {
+      access.add_read::<Components<C>>();
+    }
+  }
+}
+
+/// A filter matching the entities that have a component of type `C`.
+pub struct With<C: Component>(PhantomData<C>);
+
+impl<C: Component> QueryFilter for With<C> {
+  type Fetch<'w> = FilterFetch<'w, C>;
+
+  fn fetch<'w>(
+    structures: &'w Structures,
+    ticks: Ticks,
+    data: &Access,
+  ) -> Self::Fetch<'w> {
+    FilterFetch::new(structures, ticks, data)
+  }
+
+  fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+    fetch.contains(entity)
+  }
+
+  fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+    match fetch {
+      FilterFetch::Data => None,
+      FilterFetch::Borrowed(components) => Some(
+        components
+          .as_ref()
+          .map_or(&[], |components| &components.entities),
+      ),
+    }
+  }
+
+  fn access(access: &mut Access, data: &Access) {
+    FilterFetch::<C>::access(access, data);
+  }
+}
+
+/// A filter matching the entities that do not have a component of type `C`.
+pub struct Without<C: Component>(PhantomData<C>);
+
+impl<C: Component> QueryFilter for Without<C> {
+  type Fetch<'w> = FilterFetch<'w, C>;
+
+  fn fetch<'w>(
+    structures: &'w Structures,
+    ticks: Ticks,
+    data: &Access,
+  ) -> Self::Fetch<'w> {
+    FilterFetch::new(structures, ticks, data)
+  }
+
+  fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+    !fetch.contains(entity)
+  }
+
+  fn entities<'f>(_: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+    None
+  }
+
+  fn access(access: &mut Access, data: &Access) {
+    FilterFetch::<C>::access(access, data);
+  }
+}
+
+macro_rules! impl_query {
+  ($($d:ident),*) => {
+    #[allow(non_snake_case)]
+    impl<$($d: QueryData),*> QueryData for ($($d,)*) {
+      type Fetch<'w> = ($($d::Fetch<'w>,)*);
+
+      type Item<'f> = ($($d::Item<'f>,)*);
+
+      type ReadItem<'f> = ($($d::ReadItem<'f>,)*);
+
+      fn fetch<'w>(structures: &'w Structures, ticks: Ticks) -> Option<Self::Fetch<'w>> {
+        Some(($($d::fetch(structures, ticks)?,)*))
+      }
+
+      fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+        let ($($d,)*) = fetch;
+
+        [$($d::entities($d)),*]
+          .into_iter()
+          .flatten()
+          .min_by_key(|entities| entities.len())
+      }
+
+      fn read<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::ReadItem<'f>> {
+        let ($($d,)*) = fetch;
+
+        Some(($($d::read($d, entity)?,)*))
+      }
+
+      unsafe fn get<'f>(fetch: &'f Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'f>> {
+        let ($($d,)*) = fetch;
+
+        // SAFETY: The access of the query guarantees that each storage appears
+        // once, so the items are disjoint.
+        Some(($(unsafe { $d::get($d, entity)? },)*))
+      }
+
+      fn set_changed(fetch: &mut Self::Fetch<'_>) {
+        let ($($d,)*) = fetch;
+        $($d::set_changed($d);)*
+      }
+
+      fn access(access: &mut Access) {
+        $($d::access(access);)*
+      }
+    }
+  };
+}
+
+macro_rules! impl_query_filter {
+  ($($f:ident),*) => {
+    #[allow(unused, non_snake_case, clippy::unused_unit)]
+    impl<$($f: QueryFilter),*> QueryFilter for ($($f,)*) {
+      type Fetch<'w> = ($($f::Fetch<'w>,)*);
+
+      fn fetch<'w>(structures: &'w Structures, ticks: Ticks, data: &Access) -> Self::Fetch<'w> {
+        ($($f::fetch(structures, ticks, data),)*)
+      }
+
+      fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+        let ($($f,)*) = fetch;
+
+        true $(&& $f::matches($f, entity))*
+      }
+
+      fn entities<'f>(fetch: &'f Self::Fetch<'_>) -> Option<&'f [Entity]> {
+        let ($($f,)*) = fetch;
+
+        std::iter::empty::<&[Entity]>()
+          $(.chain($f::entities($f)))*
+          .min_by_key(|entities| entities.len())
+      }
+
+      fn access(access: &mut Access, data: &Access) {
+        $($f::access(access, data);)*
+      }
+    }
+  };
+}
+
+// Allow for 8 elements per query data and filter
+impl_query!(D1);
+impl_query!(D1, D2);
+impl_query!(D1, D2, D3);
+impl_query!(D1, D2, D3, D4);
+impl_query!(D1, D2, D3, D4, D5);
+impl_query!(D1, D2, D3, D4, D5, D6);
+impl_query!(D1, D2, D3, D4, D5, D6, D7);
+impl_query!(D1, D2, D3, D4, D5, D6, D7, D8);
+
+impl_query_filter!();
+impl_query_filter!(F1);
+impl_query_filter!(F1, F2);
+impl_query_filter!(F1, F2, F3);
+impl_query_filter!(F1, F2, F3, F4);
+impl_query_filter!(F1, F2, F3, F4, F5);
+impl_query_filter!(F1, F2, F3, F4, F5, F6);
+impl_query_filter!(F1, F2, F3, F4, F5, F6, F7);
+impl_query_filter!(F1, F2, F3, F4, F5, F6, F7, F8);
+
+/// A handler parameter giving access to the components of the entities that
+/// have all the components of `D` and match the filter `F`.
+///
+/// Each component type is stored in its own [`Components`] structure, which
+/// the query reads or writes, so that handlers querying disjoint components
+/// can run concurrently. Queries iterate the entities of their smallest
+/// storage, so they must request at least one component or have a [`With`]
+/// filter to iterate any entity.
+///
+/// ```ignore
+/// fn movement(mut query: Query<(&Velocity, &mut Position), Without<Frozen>>) {
+///   for (velocity, position) in query.iter_mut() {
+///     position.0 += velocity.0;
+///   }
+/// }
+/// ```
+pub struct Query<'w, D: QueryData, F: QueryFilter = ()> {
+  data: Option<D::Fetch<'w>>,
+  filter: F::Fetch<'w>,
+}
+
+impl<'w, D: QueryData, F: QueryFilter> Query<'w, D, F> {
+  /// Returns the [`Access`] of the data of the query, whose storages its
+  /// filters do not borrow again.
+  fn data_access() -> Access {
+    let mut data = Access::default();
+    D::access(&mut data);
+
+    data
+  }
+
+  /// Returns the entities of the smallest storage of the query, including
+  /// the storages its filters require.
+  fn candidates<'f>(
+    data: Option<&'f D::Fetch<'w>>,
+    filter: &'f F::Fetch<'w>,
+  ) -> &'f [Entity] {
+    [data.and_then(D::entities), F::entities(filter)]
+      .into_iter()
+      .flatten()
+      .min_by_key(|entities| entities.len())
+      .unwrap_or(&[])
+  }
+
+  /// Returns the read-only data of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  ///
+  /// * `->` - The data, or [`None`] if the entity does not match the query.
+  pub fn get(&self, entity: Entity) -> Option<D::ReadItem<'_>> {
+    if !F::matches(&self.filter, entity) {
+      return None;
+    }
+
+    D::read(self.data.as_ref()?, entity)
+  }
+
+  /// Returns the data of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  ///
+  /// * `->` - The data, or [`None`] if the entity does not match the query.
+  pub fn get_mut(&mut self, entity: Entity) -> Option<D::Item<'_>> {
+    if !F::matches(&self.filter, entity) {
+      return None;
+    }
+
+    let data = self.data.as_mut()?;
+    D::set_changed(data);
+
+    // SAFETY: The item borrows the query mutably, so it is the only one alive.
+    unsafe { D::get(data, entity) }
+  }
+
+  /// Returns whether an entity matches the query.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  pub fn contains(&self, entity: Entity) -> bool {
+    self.get(entity).is_some()
+  }
+
+  /// Returns an iterator over the read-only data of the matching entities.
+  pub fn iter(&self) -> impl Iterator<Item = D::ReadItem<'_>> + use<'_, 'w, D, F> {
+    let data = self.data.as_ref();
+
+    Self::candidates(data, &self.filter)
+      .iter()
+      .filter(|&&entity| F::matches(&self.filter, entity))
+      .filter_map(move |&entity| D::read(data?, entity))
+  }
+
+  /// Returns an iterator over the data of the matching entities.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = D::Item<'_>> + use<'_, 'w, D, F> {
+    if let Some(data) = self.data.as_mut() {
+      D::set_changed(data);
+    }
+
+    let data = self.data.as_ref();
+    let filter = &self.filter;
+    let candidates = Self::candidates(data, filter);
+
+    candidates
+      .iter()
+      .filter(move |&&entity| F::matches(filter, entity))
+      // SAFETY: Entities appear once per storage, so items never overlap, and
+      // they borrow the query mutably, so no other item is alive.
+      .filter_map(move |&entity| unsafe { D::get(data?, entity) })
+  }
+}
+
+impl<D: QueryData, F: QueryFilter> HandlerParam for Query<'_, D, F> {
+  /// Provides a copy of the struct with a new lifetime.
+  type Item<'new> = Query<'new, D, F>;
+
+  type Storage = ();
+
+  fn init() -> Self::Storage {}
+
+  fn retrieve<'r>(
+    _: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r> {
+    Query {
+      data: D::fetch(structures, ticks),
+      filter: F::fetch(structures, ticks, &Self::data_access()),
+    }
+  }
+
+  fn access(access: &mut Access) {
+    D::access(access);
+    F::access(access, &Self::data_access());
+  }
+}
@@ -0,0 +1,99 @@
+use crate::{
+  events::{Event, EventBus},
+  state::{BoxedError, HandlerParam, Res, ResMut, Structures, Ticks},
+};
+
+use std::any::Any;
+
+/// An error returned by a fallible handler, along with the name of the
+/// handler that returned it.
+///
+/// It is reported to the [`ErrorPolicy`] of the state once the handler has
+/// run and can be read from the [`EventBus`] when using [`ErrorPolicy::Event`].
+#[derive(Debug, Event)]
+pub struct HandlerError {
+  handler: &'static str,
+  error: BoxedError,
+}
+
+impl HandlerError {
+  /// Creates a new [`HandlerError`].
+  ///
+  /// # Arguments
+  ///
+  /// * `handler` - The name of the handler that failed.
+  /// * `error` - The error returned by the handler.
+  ///
+  /// * `->` - A new [`HandlerError`].
+  pub fn new(handler: &'static str, error: BoxedError) -> Self {
+    Self { handler, error }
+  }
+
+  /// Returns the name of the handler that failed.
+  pub fn handler(&self) -> &'static str {
+    self.handler
+  }
+
+  /// Returns the error returned by the handler.
+  pub fn error(&self) -> &BoxedError {
+    &self.error
+  }
+}
+
+impl std::fmt::Display for HandlerError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Handler {} failed: {}", self.handler, self.error)
+  }
+}
+
+impl std::error::Error for HandlerError {}
+
+/// A structure deciding what happens when a fallible handler returns an error.
+///
+/// It is read from the state whenever a handler fails, which allows it to be
+/// replaced at any time through `App::add_state`. When the state does not
+/// contain one, [`ErrorPolicy::Panic`] is used.
+#[derive(Default, Clone, Copy)]
+pub enum ErrorPolicy {
+  /// Aborts by panicking with the error.
+  #[default]
+  Panic,
+
+  /// Prints the error to the standard error output and carries on.
+  Log,
+
+  /// Writes the error as a [`HandlerError`] event onto the [`EventBus`] so
+  /// that other handlers can react to it. Falls back to [`ErrorPolicy::Log`]
+  /// when the state has no [`EventBus`].
+  Event,
+
+  /// Passes the error to a custom function.
+  Custom(fn(HandlerError)),
+}
+
+/// Reports an error returned by a handler according to the [`ErrorPolicy`] of
+/// the given structures.
+///
+/// # Arguments
+///
+/// * `structures` - A reference to the [`Structures`] of a state.
+/// * `error` - The error returned by the handler.
+/// * `tick` - The change tick of the handler run.
+pub(crate) fn report(structures: &Structures, error: HandlerError, tick: u64) {
+  let ticks = Ticks::new(tick, tick);
+  let policy = <Option<Res<ErrorPolicy>>>::retrieve(&mut (), structures, ticks)
+    .map(|policy| *policy)
+    .unwrap_or_default();
+
+  match policy {
+    ErrorPolicy::Panic => panic!("{}", error),
+    ErrorPolicy::Log => eprintln!("{}", error),
+    ErrorPolicy::Event => {
+      match <Option<ResMut<EventBus>>>::retrieve(&mut (), structures, ticks) {
+        Some(mut events) => events.write(error),
+        None => eprintln!("{}", error),
+      }
+    }
+    ErrorPolicy::Custom(f) => f(error),
+  }
+}
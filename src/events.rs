@@ -8,7 +8,10 @@ use std::{
 /// A trait for structures that can be used as events.
 ///
 /// There is a proc macro that provides automatic impl of the `Event` trait for any struct.
-pub trait Event {
+///
+/// Events must be [`Send`] and [`Sync`] so that the [`EventBus`] can be used by
+/// handlers running on any thread.
+pub trait Event: Send + Sync {
   fn as_any(self: Box<Self>) -> Box<dyn Any>
   where
     Self: 'static;
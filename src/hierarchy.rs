@@ -0,0 +1,273 @@
+use crate::{
+  app::Commands,
+  ecs::{Component, Components, Entity},
+  state::{Res, ResMut, State},
+  transform::Transform,
+};
+
+use glam::Affine2;
+
+/// A component holding the parent of an entity.
+///
+/// The [`Transform`] of an entity with a parent is relative to the one of its
+/// parent. Parents are set through [`State::set_parent`] or
+/// [`Commands::set_parent`] which keep [`Children`] in sync.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(Entity);
+
+impl Parent {
+  /// Returns the parent entity.
+  pub fn get(&self) -> Entity {
+    self.0
+  }
+}
+
+/// A component holding the children of an entity, in the order they were
+/// attached.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Children(Vec<Entity>);
+
+impl Children {
+  /// Returns the child entities.
+  pub fn get(&self) -> &[Entity] {
+    &self.0
+  }
+
+  /// Returns an iterator over the child entities.
+  pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+    self.0.iter()
+  }
+}
+
+impl State {
+  /// Returns the parent of an entity.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The child entity.
+  pub fn parent(&self, entity: Entity) -> Option<Entity> {
+    self
+      .get::<Option<Res<Components<Parent>>>>()?
+      .get(entity)
+      .map(Parent::get)
+  }
+
+  /// Returns the transform of an entity relative to the world, composing its
+  /// [`Transform`] with those of its ancestors. Entities without a
+  /// [`Transform`] do not move their children.
+  ///
+  /// Unlike [`crate::transform::GlobalTransform`], which is only propagated
+  /// once per frame, it always reflects the current transforms.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to look for.
+  ///
+  /// * `->` - The affine transformation from the entity to the world.
+  pub fn world_affine(&self, entity: Entity) -> Affine2 {
+    let transforms = self.get::<Option<Res<Components<Transform>>>>();
+    let local = |entity| {
+      transforms
+        .as_ref()
+        .and_then(|transforms| transforms.get(entity))
+        .map_or(Affine2::IDENTITY, Transform::affine)
+    };
+
+    let mut affine = local(entity);
+    let mut current = entity;
+
+    while let Some(parent) = self.parent(current) {
+      affine = local(parent) * affine;
+      current = parent;
+    }
+
+    affine
+  }
+
+  /// Attaches an entity to a parent. The [`Transform`] of the entity is made
+  /// relative to its new parent so that it keeps its world position.
+  ///
+  /// # Arguments
+  ///
+  /// * `child` - The entity to attach.
+  /// * `parent` - The new parent of the entity.
+  ///
+  /// * `->` - Whether the entity was attached, which fails if either entity
+  ///   does not exist or if the parent is the entity or one of its descendants.
+  pub fn set_parent(&mut self, child: Entity, parent: Entity) -> bool {
+    if !self.contains(child) || !self.contains(parent) {
+      return false;
+    }
+
+    // Refuse to create a cycle.
+    let mut ancestor = Some(parent);
+
+    while let Some(entity) = ancestor {
+      if entity == child {
+        return false;
+      }
+
+      ancestor = self.parent(entity);
+    }
+
+    let affine = self.world_affine(parent).inverse() * self.world_affine(child);
+
+    self.detach(child);
+    self.relocate(child, affine);
+    self.insert(child, Parent(parent));
+
+    let mut children = self.get::<Option<ResMut<Components<Children>>>>();
+
+    match children
+      .as_mut()
+      .and_then(|children| children.get_mut(parent))
+    {
+      Some(children) => children.0.push(child),
+      None => {
+        drop(children);
+        self.insert(parent, Children(vec![child]));
+      }
+    }
+
+    true
+  }
+
+  /// Detaches an entity from its parent, if any. The [`Transform`] of the
+  /// entity is made relative to the world so that it keeps its world position.
+  ///
+  /// # Arguments
+  ///
+  /// * `child` - The entity to detach.
+  pub fn remove_parent(&mut self, child: Entity) {
+    if self.parent(child).is_none() {
+      return;
+    }
+
+    let affine = self.world_affine(child);
+
+    self.detach(child);
+    self.relocate(child, affine);
+  }
+
+  /// Despawns an entity along with all of its descendants, detaching it from
+  /// its parent.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to despawn.
+  ///
+  /// * `->` - Whether the entity existed.
+  pub fn despawn_recursive(&mut self, entity: Entity) -> bool {
+    if !self.contains(entity) {
+      return false;
+    }
+
+    self.detach(entity);
+
+    let mut entities = vec![entity];
+
+    while let Some(entity) = entities.pop() {
+      if let Some(children) = self.get::<Option<Res<Components<Children>>>>() {
+        entities.extend(children.get(entity).into_iter().flat_map(Children::iter));
+      }
+
+      // The children are despawned as well, so despawning their parent does
+      // not need to detach and relocate them.
+      self.remove_components::<Children>(entity);
+      self.despawn(entity);
+    }
+
+    true
+  }
+
+  /// Detaches an entity from its parent and its children from it, keeping
+  /// the world position of its children, so that despawning it leaves no
+  /// dangling [`Parent`] or [`Children`].
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to unlink.
+  pub(crate) fn unlink(&mut self, entity: Entity) {
+    self.detach(entity);
+
+    let children = self
+      .get::<Option<Res<Components<Children>>>>()
+      .and_then(|children| children.get(entity).cloned());
+
+    for &child in children.iter().flat_map(Children::get) {
+      self.remove_parent(child);
+    }
+  }
+
+  /// Removes the [`Parent`] of an entity and the entity from the [`Children`]
+  /// of its parent.
+  fn detach(&mut self, child: Entity) {
+    let Some(parent) = self.parent(child) else {
+      return;
+    };
+
+    self.remove_components::<Parent>(child);
+
+    let mut children = self.get::<ResMut<Components<Children>>>();
+
+    if let Some(children) = children.get_mut(parent) {
+      children.0.retain(|&entity| entity != child);
+    }
+  }
+
+  /// Replaces the [`Transform`] of an entity, if it has one, with an affine
+  /// transformation.
+  fn relocate(&mut self, entity: Entity, affine: Affine2) {
+    if let Some(mut transforms) = self.get::<Option<ResMut<Components<Transform>>>>() {
+      if let Some(transform) = transforms.get_mut(entity) {
+        *transform = Transform::from_affine(affine);
+      }
+    }
+  }
+}
+
+impl Commands {
+  /// Attaches an entity to a parent, keeping its world position. See
+  /// [`State::set_parent`].
+  ///
+  /// # Arguments
+  ///
+  /// * `child` - The entity to attach.
+  /// * `parent` - The new parent of the entity.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn set_parent(&mut self, child: Entity, parent: Entity) -> &mut Self {
+    self.queue(move |state: &mut State| {
+      state.set_parent(child, parent);
+    })
+  }
+
+  /// Detaches an entity from its parent, keeping its world position. See
+  /// [`State::remove_parent`].
+  ///
+  /// # Arguments
+  ///
+  /// * `child` - The entity to detach.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn remove_parent(&mut self, child: Entity) -> &mut Self {
+    self.queue(move |state: &mut State| state.remove_parent(child))
+  }
+
+  /// Despawns an entity along with all of its descendants. See
+  /// [`State::despawn_recursive`].
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity to despawn.
+  ///
+  /// * `->` - A mutable reference to the [`Commands`].
+  #[inline]
+  pub fn despawn_recursive(&mut self, entity: Entity) -> &mut Self {
+    self.queue(move |state: &mut State| {
+      state.despawn_recursive(entity);
+    })
+  }
+}
@@ -0,0 +1,220 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A description of a handler registered in a [`ScheduleInfo`].
+pub struct HandlerInfo {
+  pub(crate) name: &'static str,
+  pub(crate) labels: Vec<&'static str>,
+  pub(crate) sets: Vec<&'static str>,
+  pub(crate) reads: Vec<&'static str>,
+  pub(crate) writes: Vec<&'static str>,
+  pub(crate) exclusive: bool,
+  pub(crate) batch: usize,
+  pub(crate) dependencies: Vec<usize>,
+}
+
+impl HandlerInfo {
+  /// Returns the type name of the handler.
+  pub fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns the labels other handlers can be ordered against.
+  pub fn labels(&self) -> &[&'static str] {
+    &self.labels
+  }
+
+  /// Returns the sets the handler belongs to.
+  pub fn sets(&self) -> &[&'static str] {
+    &self.sets
+  }
+
+  /// Returns the type names of the structures the handler and its run
+  /// conditions read, sorted alphabetically.
+  pub fn reads(&self) -> &[&'static str] {
+    &self.reads
+  }
+
+  /// Returns the type names of the structures the handler and its run
+  /// conditions write, sorted alphabetically.
+  pub fn writes(&self) -> &[&'static str] {
+    &self.writes
+  }
+
+  /// Returns whether the handler has access to the whole state and runs alone.
+  pub fn is_exclusive(&self) -> bool {
+    self.exclusive
+  }
+
+  /// Returns the index of the batch the handler runs in. Handlers of the same
+  /// batch can run concurrently.
+  pub fn batch(&self) -> usize {
+    self.batch
+  }
+
+  /// Returns the positions, within the schedule, of the handlers that must run
+  /// before this one because of ordering constraints.
+  pub fn dependencies(&self) -> &[usize] {
+    &self.dependencies
+  }
+}
+
+/// A description of a schedule, returned by
+/// [`crate::state::Scheduler::describe`].
+///
+/// Its [`Display`] implementation lists the handlers of the schedule batch by
+/// batch, along with their constraints and accesses.
+pub struct ScheduleInfo {
+  pub(crate) name: String,
+  pub(crate) handlers: Vec<HandlerInfo>,
+}
+
+impl ScheduleInfo {
+  /// Returns the name of the label of the schedule.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the handlers of the schedule in the order they run.
+  pub fn handlers(&self) -> &[HandlerInfo] {
+    &self.handlers
+  }
+}
+
+impl Display for ScheduleInfo {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.name)?;
+
+    let mut batch = None;
+
+    for handler in self.handlers.iter() {
+      if batch != Some(handler.batch) {
+        batch = Some(handler.batch);
+        writeln!(f, "  batch {}", handler.batch)?;
+      }
+
+      match handler.exclusive {
+        true => writeln!(f, "    {} (exclusive)", handler.name)?,
+        false => writeln!(f, "    {}", handler.name)?,
+      }
+
+      let after: Vec<_> = handler
+        .dependencies
+        .iter()
+        .map(|&index| self.handlers[index].name)
+        .collect();
+
+      for (title, names) in [
+        ("labels", &handler.labels),
+        ("sets", &handler.sets),
+        ("after", &after),
+        ("reads", &handler.reads),
+        ("writes", &handler.writes),
+      ] {
+        if !names.is_empty() {
+          writeln!(f, "      {}: {}", title, names.join(", "))?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Returns a textual listing of schedules.
+///
+/// # Arguments
+///
+/// * `schedules` - The descriptions of the schedules.
+///
+/// * `->` - The listing of every schedule, separated by blank lines.
+pub fn listing(schedules: &[ScheduleInfo]) -> String {
+  schedules
+    .iter()
+    .map(ScheduleInfo::to_string)
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Returns a Graphviz DOT graph of schedules.
+///
+/// Each schedule is drawn as a cluster of handlers linked by their ordering
+/// constraints. Structures are drawn outside of the clusters, with an edge
+/// from each handler writing them and a dashed edge to each handler reading
+/// them, which shows how handlers of different modules interact.
+///
+/// # Arguments
+///
+/// * `schedules` - The descriptions of the schedules.
+///
+/// * `->` - The DOT source of the graph.
+pub fn dot(schedules: &[ScheduleInfo]) -> String {
+  let mut graph = String::from("digraph schedules {\n  rankdir=LR;\n");
+  let mut edges = String::new();
+  let mut structures: Vec<&str> = Vec::new();
+
+  // Writing to a string cannot fail.
+  for (index, schedule) in schedules.iter().enumerate() {
+    let _ = writeln!(graph, "  subgraph \"cluster_{}\" {{", index);
+    let _ = writeln!(graph, "    label=\"{}\";", escape(&schedule.name));
+
+    for (position, handler) in schedule.handlers.iter().enumerate() {
+      let node = format!("{}_{}", index, position);
+      let shape = if handler.exclusive {
+        "doubleoctagon"
+      } else {
+        "box"
+      };
+
+      let _ = writeln!(
+        graph,
+        "    \"{}\" [label=\"{}\", shape={}];",
+        node,
+        escape(handler.name),
+        shape
+      );
+
+      for dependency in handler.dependencies.iter() {
+        let _ = writeln!(edges, "  \"{}_{}\" -> \"{}\";", index, dependency, node);
+      }
+
+      for &read in handler.reads.iter() {
+        let _ = writeln!(
+          edges,
+          "  \"{}\" -> \"{}\" [style=dashed];",
+          escape(read),
+          node
+        );
+      }
+
+      for &write in handler.writes.iter() {
+        let _ = writeln!(
+          edges,
+          "  \"{}\" -> \"{}\" [color=red];",
+          node,
+          escape(write)
+        );
+      }
+
+      structures.extend(handler.reads.iter().chain(handler.writes.iter()));
+    }
+
+    graph.push_str("  }\n");
+  }
+
+  structures.sort_unstable();
+  structures.dedup();
+
+  for structure in structures {
+    let _ = writeln!(graph, "  \"{}\" [shape=ellipse];", escape(structure));
+  }
+
+  graph.push_str(&edges);
+  graph.push_str("}\n");
+
+  graph
+}
+
+/// Escapes a string to be used as a quoted DOT identifier.
+fn escape(value: &str) -> String {
+  value.replace('\\', "\\\\").replace('"', "\\\"")
+}
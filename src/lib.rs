@@ -1,19 +1,30 @@
+// Allow the derive macros to refer to this crate by name from within it.
+extern crate self as charbs;
+
 pub mod app;
+pub mod app_state;
 pub mod assets;
 pub mod binding;
 pub mod buffer;
 pub mod camera;
+pub mod conditions;
+pub mod ecs;
+pub mod errors;
 pub mod events;
+pub mod hierarchy;
 pub mod input;
+pub mod introspection;
 pub mod materials;
 pub mod math;
 pub mod mesh;
 pub mod prelude;
+pub mod profiling;
 pub mod renderer;
 pub mod rendering;
 pub mod resources;
 pub mod shader;
 pub mod state;
 pub mod texture;
+pub mod time;
 pub mod transform;
 pub mod window;
@@ -3,21 +3,22 @@ use crate::{
   assets::Assets,
   binding::{BindGroup, Uniform},
   buffer::Buffer,
-  mesh::{GPUMesh, Mesh, MeshInstance, Vertex},
+  ecs::{Entities, Entity, Query},
+  mesh::{GPUMesh, Mesh, MeshInstance, Vertex, Visibility},
   prelude::RenderContext,
   renderer::GlobalBindGroup,
-  resources::Resources,
+  resources::{ResourceHandle, Resources},
   shader::Shader,
-  state::{Res, ResMut},
+  state::{IntoHandlerConfig, Res, ResMut},
   texture::Texture,
-  transform::AffineTransform,
-  window::Render,
+  transform::{AffineTransform, GlobalTransform},
+  window::{BeginRender, Render},
 };
 
 use encase::ShaderType;
 
 /// A trait that represents a material.
-pub trait Material: 'static {
+pub trait Material: Send + Sync + 'static {
   /// Returns the shader id associated with this material.
   ///
   /// TODO: Would be great to return a ShaderSource that could be built from
@@ -146,6 +147,12 @@ impl Material for TextureMaterial {
   }
 }
 
+/// A module that uploads and renders the mesh instances of a [`Material`].
+///
+/// Its update handler belongs to the `"materials"` handler set so that handlers
+/// spawning or changing mesh instances can be ordered before it. The transforms
+/// of the instances are uploaded from their [`GlobalTransform`] before
+/// rendering, and hidden instances are skipped.
 pub struct MaterialModule<M: Material> {
   _marker: std::marker::PhantomData<M>,
 }
@@ -163,78 +170,187 @@ impl<M: Material> Module for MaterialModule<M> {
     app
       .add_state(Resources::<M>::default())
       .add_state(MeshInstancesToSpawn::<M>::default())
+      .add_state(MeshInstanceChanges::<M>::default())
       .add_state(GPUMeshInstances::<M>::default())
-      .add_handler(Update, Self::update)
+      .add_handler(Update, Self::update.in_set("materials"))
+      .add_handler(BeginRender, Self::upload_transforms)
       .add_handler(Render, Self::render);
   }
 }
 
-pub(crate) type MeshInstancesToSpawn<M> = Vec<MeshInstance<M>>;
+pub(crate) type MeshInstancesToSpawn<M> = Vec<(Entity, MeshInstance<M>)>;
 
 pub(crate) type GPUMeshInstances<M> = Vec<GPUMesh<M>>;
 
+/// A change to apply to a spawned mesh instance, queued through its
+/// [`crate::mesh::InstanceHandle`].
+pub(crate) enum InstanceChange<M: Material> {
+  Mesh(ResourceHandle<Mesh>),
+  Material(ResourceHandle<M>),
+}
+
+pub(crate) type MeshInstanceChanges<M> = Vec<(Entity, InstanceChange<M>)>;
+
 impl<M: Material> MaterialModule<M> {
+  /// Creates the GPU resources of the spawned mesh instances, rebuilds those of
+  /// the changed ones and frees those of the despawned ones.
+  #[allow(clippy::too_many_arguments)]
   fn update(
     ctx: Res<RenderContext>,
     mut assets: ResMut<Assets>,
     meshes: Res<Resources<Mesh>>,
     materials: Res<Resources<M>>,
+    entities: Res<Entities>,
     mut instances: ResMut<MeshInstancesToSpawn<M>>,
+    mut changes: ResMut<MeshInstanceChanges<M>>,
     mut mesh_instances: ResMut<GPUMeshInstances<M>>,
     globals: Res<GlobalBindGroup>,
-  ) {
+  ) -> std::io::Result<()> {
     let device = ctx.device();
-    let surface = ctx.surface();
-    let adapter = ctx.adapter();
 
-    for instance in instances.drain(..) {
+    // Dropping the GPU mesh releases its buffers and pipeline.
+    mesh_instances.retain(|instance| entities.contains(instance.entity));
+
+    // An instance or a change failing to upload does not prevent the others
+    // from being uploaded, the first error is reported once they all were.
+    let mut result = Ok(());
+
+    for (entity, instance) in instances.drain(..) {
+      // The instance may have been despawned before it was ever uploaded.
+      if !entities.contains(entity) {
+        continue;
+      }
+
       let transform_uniform =
         Uniform::new(device, AffineTransform::from(instance.transform));
       let bind_group = BindGroup::new(device, vec![&transform_uniform]);
 
       let mesh = meshes.get(&instance.mesh).unwrap();
       let material = materials.get(&instance.material).unwrap();
-      let shader_source = assets.get(M::shader());
-      let shader = Shader::new(device, shader_source.as_ref());
-
-      // Create the pipeline layout for the mesh
-      let pipeline_layout =
-        device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-          label: None,
-          bind_group_layouts: &[
-            globals.layout(),
-            bind_group.layout(),
-            material.bind_group().layout(),
-          ],
-          push_constant_ranges: &[],
-        });
-
-      // Create the vertex state
-      let vertex = wgpu::VertexState {
-        entry_point: "vertex_main",
-        module: shader.inner(),
-        buffers: &[Vertex::buffer_layout()],
-        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      let pipeline =
+        match Self::create_pipeline(&ctx, &mut assets, &globals, &bind_group, material) {
+          Ok(pipeline) => pipeline,
+          Err(error) => {
+            result = result.and(Err(error));
+            continue;
+          }
+        };
+      let (vertex_buffer, index_buffer) = Self::create_buffers(device, mesh);
+
+      mesh_instances.push(GPUMesh {
+        entity,
+        pipeline,
+        material: instance.material,
+        transform: transform_uniform,
+        uploaded: GlobalTransform::from(instance.transform),
+        bind_group,
+        vertex_buffer,
+        index_buffer,
+      });
+    }
+
+    for (entity, change) in changes.drain(..) {
+      let Some(instance) = mesh_instances
+        .iter_mut()
+        .find(|instance| instance.entity == entity)
+      else {
+        continue;
       };
 
-      // Create the targets for the fragment
-      // TODO: Should most likely be configurable in a material description?
-      let targets = [Some(wgpu::ColorTargetState {
-        format: *surface.get_capabilities(adapter).formats.first().unwrap(),
-        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-        write_mask: wgpu::ColorWrites::ALL,
-      })];
-
-      // Create the fragment state
-      let fragment = Some(wgpu::FragmentState {
-        entry_point: "fragment_main",
-        module: shader.inner(),
-        targets: &targets,
-        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      match change {
+        InstanceChange::Mesh(mesh) => {
+          let mesh = meshes.get(&mesh).unwrap();
+          (instance.vertex_buffer, instance.index_buffer) =
+            Self::create_buffers(device, mesh);
+        }
+        InstanceChange::Material(handle) => {
+          let material = materials.get(&handle).unwrap();
+
+          // The instance keeps its previous material if the new one fails.
+          match Self::create_pipeline(
+            &ctx,
+            &mut assets,
+            &globals,
+            &instance.bind_group,
+            material,
+          ) {
+            Ok(pipeline) => {
+              instance.pipeline = pipeline;
+              instance.material = handle;
+            }
+            Err(error) => result = result.and(Err(error)),
+          }
+        }
+      }
+    }
+
+    result
+  }
+
+  /// Creates the render pipeline of a mesh instance.
+  ///
+  /// # Arguments
+  ///
+  /// * `ctx` - The rendering context used to create the pipeline.
+  /// * `assets` - The assets to load the shader of the material from.
+  /// * `globals` - The bind group shared by every mesh instance.
+  /// * `bind_group` - The bind group of the mesh instance.
+  /// * `material` - The material of the mesh instance.
+  ///
+  /// * `->` - The render pipeline, or an error if the shader cannot be loaded.
+  fn create_pipeline(
+    ctx: &RenderContext,
+    assets: &mut Assets,
+    globals: &GlobalBindGroup,
+    bind_group: &BindGroup,
+    material: &M,
+  ) -> std::io::Result<wgpu::RenderPipeline> {
+    let device = ctx.device();
+    let surface = ctx.surface();
+    let adapter = ctx.adapter();
+
+    let shader_source = assets.get(M::shader())?;
+    let shader = Shader::new(device, shader_source.as_ref());
+
+    // Create the pipeline layout for the mesh
+    let pipeline_layout =
+      device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[
+          globals.layout(),
+          bind_group.layout(),
+          material.bind_group().layout(),
+        ],
+        push_constant_ranges: &[],
       });
 
-      // Create the render pipeline using the layout and the material
-      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+    // Create the vertex state
+    let vertex = wgpu::VertexState {
+      entry_point: "vertex_main",
+      module: shader.inner(),
+      buffers: &[Vertex::buffer_layout()],
+      compilation_options: wgpu::PipelineCompilationOptions::default(),
+    };
+
+    // Create the targets for the fragment
+    // TODO: Should most likely be configurable in a material description?
+    let targets = [Some(wgpu::ColorTargetState {
+      format: *surface.get_capabilities(adapter).formats.first().unwrap(),
+      blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+      write_mask: wgpu::ColorWrites::ALL,
+    })];
+
+    // Create the fragment state
+    let fragment = Some(wgpu::FragmentState {
+      entry_point: "fragment_main",
+      module: shader.inner(),
+      targets: &targets,
+      compilation_options: wgpu::PipelineCompilationOptions::default(),
+    });
+
+    // Create the render pipeline using the layout and the material
+    Ok(
+      device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
         label: None,
         layout: Some(&pipeline_layout),
         vertex,
@@ -244,26 +360,49 @@ impl<M: Material> MaterialModule<M> {
         cache: None,
         primitive: wgpu::PrimitiveState::default(),
         multisample: wgpu::MultisampleState::default(),
-      });
+      }),
+    )
+  }
 
-      // TODO: Must store the pipeline and bind group for actual rendering.
-      mesh_instances.push(GPUMesh {
-        pipeline,
-        material: instance.material,
-        bind_group,
+  /// Creates the vertex and index buffers of a mesh.
+  ///
+  /// # Arguments
+  ///
+  /// * `device` - The [`wgpu::Device`] to create the buffers with.
+  /// * `mesh` - The mesh to upload.
+  ///
+  /// * `->` - The vertex and index buffers.
+  fn create_buffers(device: &wgpu::Device, mesh: &Mesh) -> (Buffer<Vertex>, Buffer<u16>) {
+    (
+      Buffer::new_with_data(device, wgpu::BufferUsages::VERTEX, &mesh.vertices),
+      Buffer::new_with_data(device, wgpu::BufferUsages::INDEX, &mesh.indices),
+    )
+  }
 
-        vertex_buffer: Buffer::new_with_data(
-          device,
-          wgpu::BufferUsages::VERTEX,
-          &mesh.vertices,
-        ),
-
-        index_buffer: Buffer::new_with_data(
-          device,
-          wgpu::BufferUsages::INDEX,
-          &mesh.indices,
-        ),
-      });
+  /// Uploads the global transforms of the mesh instances that changed since
+  /// they were last uploaded to the GPU.
+  ///
+  /// # Arguments
+  ///
+  /// * `ctx` - The rendering context used to write the transform uniforms.
+  /// * `globals` - The global transforms of the entities.
+  /// * `mesh_instances` - The mesh instances to upload the transforms of.
+  fn upload_transforms(
+    ctx: Res<RenderContext>,
+    globals: Query<&GlobalTransform>,
+    mut mesh_instances: ResMut<GPUMeshInstances<M>>,
+  ) {
+    for instance in mesh_instances.bypass_change_detection().iter_mut() {
+      let Some(&global) = globals.get(instance.entity) else {
+        continue;
+      };
+
+      if instance.uploaded != global {
+        instance.uploaded = global;
+        instance
+          .transform
+          .update(ctx.queue(), |transform| *transform = global.into());
+      }
     }
   }
 
@@ -276,6 +415,8 @@ impl<M: Material> MaterialModule<M> {
     mut ctx: ResMut<RenderContext>,
     materials: Res<Resources<M>>,
     mesh_instances: Res<GPUMeshInstances<M>>,
+    entities: Res<Entities>,
+    visibilities: Query<&Visibility>,
     globals: Res<GlobalBindGroup>,
   ) {
     if mesh_instances.is_empty() {
@@ -285,6 +426,14 @@ impl<M: Material> MaterialModule<M> {
     let mut render_pass = ctx.current_frame_mut().create_render_pass();
 
     for instance in mesh_instances.iter() {
+      // Instances despawned since the last update are only dropped by the
+      // next one, but must not be drawn anymore.
+      if !entities.contains(instance.entity)
+        || visibilities.get(instance.entity) == Some(&Visibility::Hidden)
+      {
+        continue;
+      }
+
       let material = materials.get(&instance.material).unwrap();
 
       // Prepare the shader program
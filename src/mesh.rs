@@ -1,22 +1,35 @@
 use crate::{
-  binding::BindGroup,
+  app::Commands,
+  binding::{BindGroup, Uniform},
   buffer::Buffer,
-  materials::Material,
+  ecs::{Component, Entity, Query},
+  materials::{InstanceChange, Material, MeshInstanceChanges},
   math::{Circle, Rectangle, Triangle},
   resources::ResourceHandle,
-  transform::Transform,
+  state::{ResMut, State},
+  transform::{AffineTransform, GlobalTransform, Transform},
 };
 
 use bytemuck::{Pod, Zeroable};
 
+use std::{
+  marker::PhantomData,
+  sync::{Arc, OnceLock},
+};
+
 // A structure that represents a single Vertex buffer for the gpu.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[derive(Debug, Clone, Copy)]
 pub struct Vertex {
   position: [f32; 2],
   uv: [f32; 2],
 }
 
+// SAFETY: The vertex only holds `f32` arrays laid out without padding. The
+// traits are not derived as the derived checks are reported as dead code.
+unsafe impl Zeroable for Vertex {}
+unsafe impl Pod for Vertex {}
+
 impl Vertex {
   /// Returns a static description of the vertex buffer layout.
   pub fn buffer_layout() -> wgpu::VertexBufferLayout<'static> {
@@ -119,16 +132,198 @@ impl From<Circle> for Mesh {
   }
 }
 
+/// A mesh with a material to be spawned as an entity.
+///
+/// The transform is the initial [`Transform`] component of the entity, which
+/// may be attached to a parent afterwards.
 pub struct MeshInstance<M: Material> {
   pub mesh: ResourceHandle<Mesh>,
   pub material: ResourceHandle<M>,
   pub transform: Transform,
-  // transform uniform should be here ?
+}
+
+/// A component telling whether a mesh instance is rendered. It does not affect
+/// the children of the instance.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+  #[default]
+  Visible,
+  Hidden,
+}
+
+/// A handle to a [`MeshInstance`] spawned through
+/// [`crate::app::Commands::spawn`].
+///
+/// The entity of the instance is only created once the commands are applied,
+/// at the end of the schedule that spawned it. Until then, the handle does not
+/// resolve to any entity. Handles can be cloned and stored in the state so that
+/// handlers can read and mutate the [`Transform`] of the instance.
+///
+/// Changes queued through a handle are applied along with the other
+/// [`Commands`], and the GPU resources of the instance are freed or rebuilt
+/// accordingly before the next frame is rendered.
+pub struct InstanceHandle<M: Material> {
+  entity: Arc<OnceLock<Entity>>,
+  _marker: PhantomData<M>,
+}
+
+impl<M: Material> Clone for InstanceHandle<M> {
+  fn clone(&self) -> Self {
+    Self {
+      entity: self.entity.clone(),
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<M: Material> InstanceHandle<M> {
+  /// Creates a new unresolved [`InstanceHandle`].
+  pub(crate) fn new() -> Self {
+    Self {
+      entity: Arc::new(OnceLock::new()),
+      _marker: PhantomData,
+    }
+  }
+
+  /// Resolves the handle to the entity of its instance once it is spawned.
+  ///
+  /// # Arguments
+  ///
+  /// * `entity` - The entity of the instance.
+  pub(crate) fn resolve(&self, entity: Entity) {
+    // Handles are resolved once, when their spawn command is applied.
+    let _ = self.entity.set(entity);
+  }
+
+  /// Returns the entity of the instance, or [`None`] if it was not spawned
+  /// yet.
+  pub fn entity(&self) -> Option<Entity> {
+    self.entity.get().copied()
+  }
+
+  /// Returns the transform of the instance, relative to its parent if any.
+  ///
+  /// # Arguments
+  ///
+  /// * `transforms` - The transforms of the entities.
+  ///
+  /// * `->` - The transform, or [`None`] if the instance was not spawned yet.
+  pub fn transform<'q>(
+    &self,
+    transforms: &'q Query<&Transform>,
+  ) -> Option<&'q Transform> {
+    transforms.get(self.entity()?)
+  }
+
+  /// Returns a mutable reference to the transform of the instance, relative to
+  /// its parent if any. Changes are uploaded to the GPU before the next frame is
+  /// rendered.
+  ///
+  /// # Arguments
+  ///
+  /// * `transforms` - The transforms of the entities.
+  ///
+  /// * `->` - The transform, or [`None`] if the instance was not spawned yet.
+  pub fn transform_mut<'q>(
+    &self,
+    transforms: &'q mut Query<&mut Transform>,
+  ) -> Option<&'q mut Transform> {
+    transforms.get_mut(self.entity()?)
+  }
+
+  /// Returns whether the instance is rendered.
+  ///
+  /// # Arguments
+  ///
+  /// * `visibilities` - The visibilities of the entities.
+  ///
+  /// * `->` - The visibility, or [`None`] if the instance was not spawned yet or
+  ///   was despawned.
+  pub fn is_visible(&self, visibilities: &Query<&Visibility>) -> Option<bool> {
+    Some(*visibilities.get(self.entity()?)? == Visibility::Visible)
+  }
+
+  /// Shows or hides the instance without freeing its GPU resources.
+  ///
+  /// # Arguments
+  ///
+  /// * `commands` - The [`Commands`] to queue the change with.
+  /// * `visible` - Whether the instance is rendered.
+  pub fn set_visible(&self, commands: &mut Commands, visible: bool) {
+    let visibility = match visible {
+      true => Visibility::Visible,
+      false => Visibility::Hidden,
+    };
+
+    let handle = self.clone();
+    commands.queue(move |state: &mut State| {
+      if let Some(entity) = handle.entity() {
+        state.insert(entity, visibility);
+      }
+    });
+  }
+
+  /// Replaces the mesh of the instance, rebuilding its vertex and index
+  /// buffers.
+  ///
+  /// # Arguments
+  ///
+  /// * `commands` - The [`Commands`] to queue the change with.
+  /// * `mesh` - The new mesh of the instance.
+  pub fn set_mesh(&self, commands: &mut Commands, mesh: ResourceHandle<Mesh>) {
+    self.change(commands, InstanceChange::Mesh(mesh));
+  }
+
+  /// Replaces the material of the instance, rebuilding its render pipeline.
+  ///
+  /// # Arguments
+  ///
+  /// * `commands` - The [`Commands`] to queue the change with.
+  /// * `material` - The new material of the instance.
+  pub fn set_material(&self, commands: &mut Commands, material: ResourceHandle<M>) {
+    self.change(commands, InstanceChange::Material(material));
+  }
+
+  /// Despawns the instance along with its descendants, freeing their GPU
+  /// resources.
+  ///
+  /// # Arguments
+  ///
+  /// * `commands` - The [`Commands`] to queue the despawn with.
+  pub fn despawn(&self, commands: &mut Commands) {
+    let handle = self.clone();
+    commands.queue(move |state: &mut State| {
+      if let Some(entity) = handle.entity() {
+        state.despawn_recursive(entity);
+      }
+    });
+  }
+
+  /// Queues a change to be applied to the GPU resources of the instance.
+  fn change(&self, commands: &mut Commands, change: InstanceChange<M>) {
+    let handle = self.clone();
+    commands.queue(move |state: &mut State| {
+      let Some(entity) = handle.entity().filter(|&entity| state.contains(entity)) else {
+        return;
+      };
+
+      if !state.has::<MeshInstanceChanges<M>>() {
+        state.add(MeshInstanceChanges::<M>::default());
+      }
+
+      state
+        .get::<ResMut<MeshInstanceChanges<M>>>()
+        .push((entity, change));
+    });
+  }
 }
 
 pub(crate) struct GPUMesh<M: Material> {
+  pub entity: Entity,
   pub material: ResourceHandle<M>,
   pub pipeline: wgpu::RenderPipeline,
+  pub transform: Uniform<AffineTransform>,
+  pub uploaded: GlobalTransform,
   pub bind_group: BindGroup,
 
   pub vertex_buffer: Buffer<Vertex>,
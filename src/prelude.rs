@@ -1,9 +1,33 @@
-pub use crate::app::{App, Init, Module, Update};
+pub use crate::app::{
+  App, Command, Commands, FixedUpdate, Init, Last, Module, PostUpdate, PreInit,
+  PreUpdate, Update,
+};
+
+pub use crate::app_state::{AppState, NextState, OnEnter, OnExit, OnUpdate, States};
+
+pub use crate::conditions::{
+  Condition, in_state, resource_added, resource_changed, resource_equals, resource_exists,
+};
+
+pub use crate::ecs::{
+  Bundle, Component, Components, Entities, Entity, Query, With, Without,
+};
+
+pub use crate::errors::{ErrorPolicy, HandlerError};
+
+pub use crate::hierarchy::{Children, Parent};
+
+pub use crate::profiling::Profiler;
 
 pub use crate::rendering::{RenderContext, RenderModule};
 
 pub use crate::state::{
-  Handler, IntoHandler, Res, ResMut, ScheduleLabel, Scheduler, State,
+  BoxedError, Handler, HandlerId, HandlerParam, HandlerSet, IntoHandler,
+  IntoHandlerConfig, Local, Res, ResMut, ScheduleLabel, Scheduler, State,
 };
 
+pub use crate::time::{FixedTime, Stopwatch, Time, Timer, TimerMode};
+
+pub use crate::transform::{GlobalTransform, Transform};
+
 pub use crate::window::WindowModule;
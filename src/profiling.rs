@@ -0,0 +1,307 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  fmt::Write,
+  io,
+  path::Path,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// A structure holding the durations of the last runs of a schedule or a
+/// handler, up to the window of the [`Profiler`].
+pub struct Timings {
+  samples: VecDeque<Duration>,
+  window: usize,
+}
+
+impl Timings {
+  /// Creates new empty [`Timings`].
+  fn new(window: usize) -> Self {
+    Self {
+      samples: VecDeque::with_capacity(window),
+      window,
+    }
+  }
+
+  /// Records a new duration, dropping the oldest one if the window is full.
+  fn push(&mut self, duration: Duration) {
+    if self.samples.len() == self.window {
+      self.samples.pop_front();
+    }
+
+    self.samples.push_back(duration);
+  }
+
+  /// Returns the number of recorded durations.
+  pub fn len(&self) -> usize {
+    self.samples.len()
+  }
+
+  /// Returns whether no duration was recorded.
+  pub fn is_empty(&self) -> bool {
+    self.samples.is_empty()
+  }
+
+  /// Returns the most recent duration.
+  pub fn last(&self) -> Duration {
+    self.samples.back().copied().unwrap_or_default()
+  }
+
+  /// Returns the mean of the recorded durations.
+  pub fn mean(&self) -> Duration {
+    match self.samples.len() {
+      0 => Duration::ZERO,
+      len => self.samples.iter().sum::<Duration>() / len as u32,
+    }
+  }
+
+  /// Returns the shortest recorded duration.
+  pub fn min(&self) -> Duration {
+    self.samples.iter().min().copied().unwrap_or_default()
+  }
+
+  /// Returns the longest recorded duration.
+  pub fn max(&self) -> Duration {
+    self.samples.iter().max().copied().unwrap_or_default()
+  }
+}
+
+/// A single timed run within a trace.
+struct TraceEvent {
+  name: String,
+  category: &'static str,
+  start: Duration,
+  duration: Duration,
+  thread: usize,
+}
+
+/// A structure recording how long schedules and their handlers take to run.
+///
+/// Instrumentation is optional: schedules are only timed while a [`Profiler`]
+/// is part of the [`crate::state::State`]. It keeps rolling statistics over the
+/// last runs of every schedule and handler, and can additionally record a trace
+/// to be exported in the Chrome `trace_event` format and opened in
+/// `chrome://tracing` or Perfetto.
+///
+/// Schedules are identified by the name of their label, which includes the
+/// value of labels with fields, so that `Level(1)` and `Level(2)` are timed
+/// separately.
+pub struct Profiler {
+  epoch: Instant,
+  window: usize,
+  schedules: HashMap<String, (Timings, HashMap<&'static str, Timings>)>,
+  trace: Vec<TraceEvent>,
+  tracing: bool,
+}
+
+impl Default for Profiler {
+  fn default() -> Self {
+    Self::new(120)
+  }
+}
+
+impl Profiler {
+  /// Creates a new [`Profiler`].
+  ///
+  /// # Arguments
+  ///
+  /// * `window` - The number of runs statistics are computed over.
+  ///
+  /// * `->` - A new [`Profiler`].
+  pub fn new(window: usize) -> Self {
+    assert!(window > 0, "The profiler window must not be empty.");
+
+    Self {
+      epoch: Instant::now(),
+      window,
+      schedules: HashMap::new(),
+      trace: Vec::new(),
+      tracing: false,
+    }
+  }
+
+  /// Returns the timings of a schedule.
+  ///
+  /// # Arguments
+  ///
+  /// * `schedule` - The name of the label of the schedule.
+  pub fn schedule(&self, schedule: &str) -> Option<&Timings> {
+    self.schedules.get(schedule).map(|(timings, _)| timings)
+  }
+
+  /// Returns the timings of a handler within a schedule.
+  ///
+  /// # Arguments
+  ///
+  /// * `schedule` - The name of the label of the schedule.
+  /// * `handler` - The type name of the handler.
+  pub fn handler(&self, schedule: &str, handler: &str) -> Option<&Timings> {
+    self
+      .schedules
+      .get(schedule)
+      .and_then(|(_, handlers)| handlers.get(handler))
+  }
+
+  /// Returns an iterator over the timings of every handler along with the names
+  /// of the handler and its schedule.
+  pub fn handlers(&self) -> impl Iterator<Item = (&str, &'static str, &Timings)> {
+    self.schedules.iter().flat_map(|(schedule, (_, handlers))| {
+      handlers
+        .iter()
+        .map(move |(handler, timings)| (schedule.as_str(), *handler, timings))
+    })
+  }
+
+  /// Starts recording a trace, on top of the statistics.
+  pub fn start_trace(&mut self) {
+    self.tracing = true;
+  }
+
+  /// Stops recording the trace. The recorded trace is kept until cleared.
+  pub fn stop_trace(&mut self) {
+    self.tracing = false;
+  }
+
+  /// Discards the recorded trace.
+  pub fn clear_trace(&mut self) {
+    self.trace.clear();
+  }
+
+  /// Returns the recorded trace in the Chrome `trace_event` JSON format.
+  pub fn trace_json(&self) -> String {
+    let mut json = String::from("{\"traceEvents\":[");
+
+    // Writing to a string cannot fail.
+    for (index, event) in self.trace.iter().enumerate() {
+      if index > 0 {
+        json.push(',');
+      }
+
+      let _ = write!(
+        json,
+        "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{}}}",
+        escape(&event.name),
+        event.category,
+        event.start.as_micros(),
+        event.duration.as_micros(),
+        event.thread
+      );
+    }
+
+    json.push_str("]}");
+    json
+  }
+
+  /// Writes the recorded trace to a file in the Chrome `trace_event` JSON
+  /// format.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - The path of the file to write.
+  ///
+  /// * `->` - An error if the file cannot be written.
+  pub fn write_trace(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::write(path, self.trace_json())
+  }
+
+  /// Records a run of a schedule along with the runs of its handlers.
+  ///
+  /// # Arguments
+  ///
+  /// * `schedule` - The name of the label of the schedule.
+  /// * `start` - The instant the schedule started running.
+  /// * `recorder` - The [`Recorder`] that timed the handlers.
+  pub(crate) fn record(&mut self, schedule: &str, start: Instant, recorder: Recorder) {
+    let duration = start.elapsed();
+    let window = self.window;
+
+    let (timings, handlers) = self
+      .schedules
+      .entry(schedule.to_string())
+      .or_insert_with(|| (Timings::new(window), HashMap::new()));
+
+    timings.push(duration);
+
+    let spans = recorder.spans.into_inner().unwrap();
+
+    for span in spans.iter() {
+      handlers
+        .entry(span.name)
+        .or_insert_with(|| Timings::new(window))
+        .push(span.duration);
+    }
+
+    if !self.tracing {
+      return;
+    }
+
+    self.trace.push(TraceEvent {
+      name: schedule.to_string(),
+      category: "schedule",
+      start: start.duration_since(self.epoch),
+      duration,
+      thread: 0,
+    });
+
+    self.trace.extend(spans.into_iter().map(|span| TraceEvent {
+      name: span.name.to_string(),
+      category: "handler",
+      start: span.start.duration_since(self.epoch),
+      duration: span.duration,
+      thread: span.thread,
+    }));
+  }
+}
+
+/// A timed run of a handler.
+struct Span {
+  name: &'static str,
+  start: Instant,
+  duration: Duration,
+  thread: usize,
+}
+
+/// A structure collecting the runs of the handlers of a schedule, possibly
+/// from several threads, before they are recorded by the [`Profiler`].
+#[derive(Default)]
+pub(crate) struct Recorder {
+  spans: Mutex<Vec<Span>>,
+}
+
+impl Recorder {
+  /// Runs a handler, timing it if a recorder is given.
+  ///
+  /// # Arguments
+  ///
+  /// * `recorder` - The [`Recorder`] to record the run with, if any.
+  /// * `name` - The type name of the handler.
+  /// * `run` - The function running the handler.
+  ///
+  /// * `->` - The result of the run.
+  pub(crate) fn time<T>(
+    recorder: Option<&Self>,
+    name: &'static str,
+    run: impl FnOnce() -> T,
+  ) -> T {
+    let Some(recorder) = recorder else {
+      return run();
+    };
+
+    let start = Instant::now();
+    let result = run();
+
+    recorder.spans.lock().unwrap().push(Span {
+      name,
+      start,
+      duration: start.elapsed(),
+      thread: rayon::current_thread_index().map_or(0, |index| index + 1),
+    });
+
+    result
+  }
+}
+
+/// Escapes a string to be used within a JSON string.
+fn escape(value: &str) -> String {
+  value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -7,7 +7,7 @@ use crate::{
   rendering::RenderContext,
   resources::Resources,
   state::{Res, ResMut},
-  window::{Window, WindowResized},
+  window::{BeginRender, Window, WindowResized},
 };
 
 pub(crate) type GlobalBindGroup = BindGroup;
@@ -20,7 +20,8 @@ impl Module for RendererModule {
     app
       .add_state(Resources::<Mesh>::default())
       .add_handler(Init, Self::init)
-      .add_handler(Update, Self::resize);
+      .add_handler(Update, Self::resize)
+      .add_handler(BeginRender, Self::upload_camera);
   }
 }
 
@@ -55,10 +56,11 @@ impl RendererModule {
   ///
   /// * `event_bus` - The event bus to read window resized events.
   /// * `ctx` - The rendering context to resize.
+  /// * `camera` - The main camera, if it has been initialized yet.
   pub fn resize(
     mut event_bus: ResMut<EventBus>,
     ctx: ResMut<RenderContext>,
-    mut camera: ResMut<Camera>,
+    mut camera: Option<ResMut<Camera>>,
   ) {
     for WindowResized { width, height } in event_bus.read::<WindowResized>() {
       if width > 0 && height > 0 {
@@ -66,10 +68,24 @@ impl RendererModule {
         ctx.resize(width, height);
 
         // Resize camera and update mouse window size
-        camera.set_aspect(width as f32 / height as f32);
-        camera.update(ctx.queue());
+        if let Some(camera) = camera.as_mut() {
+          camera.set_aspect(width as f32 / height as f32);
+        }
         // TODO: Update mouse window size
       }
     }
   }
+
+  /// Uploads the camera to the GPU whenever it has changed since the last
+  /// frame.
+  ///
+  /// # Arguments
+  ///
+  /// * `ctx` - The rendering context used to write the camera uniform.
+  /// * `camera` - The main camera, if it has been initialized yet.
+  pub fn upload_camera(ctx: Res<RenderContext>, camera: Option<ResMut<Camera>>) {
+    if let Some(mut camera) = camera.filter(|camera| camera.is_changed()) {
+      camera.bypass_change_detection().update(ctx.queue());
+    }
+  }
 }
@@ -1,6 +1,6 @@
 use crate::{
   app::{App, Commands, Module, PreInit},
-  state::{Res, ResMut},
+  state::{BoxedError, Res, ResMut},
   window::{BeginRender, EndRender, Window},
 };
 
@@ -246,14 +246,19 @@ impl RenderModule {
   ///
   /// * `commands` - A mutable reference to the [`Commands`] dispatcher.
   /// * `window` - The [`Window`] struct representing the main window.
-  fn pre_init(mut commands: ResMut<Commands>, window: Res<Window>) {
+  ///
+  /// * `->` - An error if no compatible graphics device could be set up.
+  fn pre_init(
+    mut commands: ResMut<Commands>,
+    window: Res<Window>,
+  ) -> Result<(), BoxedError> {
     // Create a new instance of a wgpu instance to create our surface from the
     // newly created window and the adapter that will be used to create our
     // rendering context
     let wgpu = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
 
     // Create a new wgpu surface from an active window handle.
-    let surface = wgpu.create_surface(window.arc()).unwrap();
+    let surface = wgpu.create_surface(window.arc())?;
 
     // Request an adapter that is compatible with the newly created surface and
     // that ideally is a discrete GPU with high performance
@@ -265,8 +270,8 @@ impl RenderModule {
           force_fallback_adapter: false,
         })
         .await
-        .unwrap()
-    });
+        .ok_or("Cannot find a graphics adapter compatible with the window.")
+    })?;
 
     // Request a device and a command queue from our adapter
     let (device, queue) = pollster::block_on(async {
@@ -281,8 +286,7 @@ impl RenderModule {
           None,
         )
         .await
-        .unwrap()
-    });
+    })?;
 
     // Create a new instance of our render context
     let ctx = RenderContext::new(adapter, device, queue, surface);
@@ -290,6 +294,8 @@ impl RenderModule {
 
     // Store in the internal state
     commands.add_state(ctx);
+
+    Ok(())
   }
 
   fn begin_render(mut ctx: ResMut<RenderContext>) {
@@ -1,13 +1,213 @@
-pub use charbs_macros::ScheduleLabel;
+pub use charbs_macros::{HandlerParam, ScheduleLabel};
+
+use crate::{
+  errors::{self, HandlerError},
+  introspection::{HandlerInfo, ScheduleInfo},
+  profiling::{Profiler, Recorder},
+};
 
 use std::{
   any::{Any, TypeId},
-  cell::{Ref, RefCell, RefMut},
   collections::HashMap,
+  hash::{Hash, Hasher},
   marker::PhantomData,
+  mem::ManuallyDrop,
   ops::{Deref, DerefMut},
+  sync::{
+    Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError,
+    atomic::{AtomicU64, Ordering},
+  },
+  thread::ThreadId,
+  time::Instant,
 };
 
+/// A type alias for the storage of a [`State`], mapping each structure type to
+/// its [`Structure`].
+pub type Structures = HashMap<TypeId, Structure>;
+
+/// A single structure stored within a [`State`].
+///
+/// Structures are guarded by a read-write lock so that handlers touching
+/// different structures can run on different threads. Structures that are not
+/// [`Send`] and [`Sync`] remember the thread that created them and can only be
+/// accessed and dropped from that thread.
+///
+/// Structures also keep track of the change ticks at which they were added and
+/// last mutably dereferenced, which allows handlers to detect changes.
+pub struct Structure {
+  value: ManuallyDrop<RwLock<Box<dyn Any>>>,
+  name: &'static str,
+  owner: Option<ThreadId>,
+  added: u64,
+  changed: AtomicU64,
+}
+
+// SAFETY: Structures can only be created from `Send + Sync` values, unless they
+// have an owner thread in which case every access and drop is checked against
+// it.
+unsafe impl Send for Structure {}
+unsafe impl Sync for Structure {}
+
+impl Structure {
+  /// Creates a new thread-safe [`Structure`].
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The value to be stored.
+  /// * `tick` - The change tick at which the structure is added.
+  ///
+  /// * `->` - A new [`Structure`] that can be accessed from any thread.
+  fn new<R: Send + Sync + 'static>(value: R, tick: u64) -> Self {
+    Self {
+      value: ManuallyDrop::new(RwLock::new(Box::new(value))),
+      name: std::any::type_name::<R>(),
+      owner: None,
+      added: tick,
+      changed: AtomicU64::new(tick),
+    }
+  }
+
+  /// Creates a new [`Structure`] bound to the current thread.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The value to be stored.
+  /// * `tick` - The change tick at which the structure is added.
+  ///
+  /// * `->` - A new [`Structure`] that can only be accessed from this thread.
+  fn new_non_send<R: 'static>(value: R, tick: u64) -> Self {
+    Self {
+      value: ManuallyDrop::new(RwLock::new(Box::new(value))),
+      name: std::any::type_name::<R>(),
+      owner: Some(std::thread::current().id()),
+      added: tick,
+      changed: AtomicU64::new(tick),
+    }
+  }
+
+  /// Returns the type name of the stored structure.
+  pub fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns whether the structure can be accessed from any thread.
+  pub fn is_send(&self) -> bool {
+    self.owner.is_none()
+  }
+
+  /// Returns the change tick at which the structure was added.
+  pub fn added(&self) -> u64 {
+    self.added
+  }
+
+  /// Returns the change tick at which the structure was last changed.
+  pub fn changed(&self) -> u64 {
+    self.changed.load(Ordering::Relaxed)
+  }
+
+  /// Takes the value out of the structure.
+  ///
+  /// Panics if the structure is accessed from a thread it is not bound to.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The stored value.
+  fn into_value(mut self) -> Box<dyn Any> {
+    assert!(
+      self.is_accessible(),
+      "Structure {} can only be accessed from the thread that created it.",
+      self.name
+    );
+
+    // SAFETY: The value is taken out once and the structure is forgotten right
+    // after so that it is not dropped twice.
+    let value = unsafe { ManuallyDrop::take(&mut self.value) };
+    std::mem::forget(self);
+
+    value
+      .into_inner()
+      .unwrap_or_else(std::sync::PoisonError::into_inner)
+  }
+
+  /// Marks the structure as added, and therefore changed, at a change tick.
+  ///
+  /// # Arguments
+  ///
+  /// * `tick` - The change tick at which the structure is added.
+  fn set_added(&mut self, tick: u64) {
+    self.added = tick;
+    self.changed = AtomicU64::new(tick);
+  }
+
+  /// Returns whether the structure can be accessed from the current thread.
+  fn is_accessible(&self) -> bool {
+    self
+      .owner
+      .is_none_or(|owner| owner == std::thread::current().id())
+  }
+
+  /// Returns a read-only guard to the stored structure.
+  ///
+  /// Panics if the structure is already mutably borrowed or if it is accessed
+  /// from a thread it is bound to.
+  pub(crate) fn read(&self) -> RwLockReadGuard<'_, Box<dyn Any>> {
+    assert!(
+      self.is_accessible(),
+      "Structure {} can only be accessed from the thread that created it.",
+      self.name
+    );
+
+    match self.value.try_read() {
+      Ok(guard) => guard,
+      Err(TryLockError::Poisoned(error)) => error.into_inner(),
+      Err(TryLockError::WouldBlock) => {
+        panic!("Structure {} is already mutably borrowed.", self.name)
+      }
+    }
+  }
+
+  /// Returns a mutable guard to the stored structure.
+  ///
+  /// Panics if the structure is already borrowed or if it is accessed from a
+  /// thread it is bound to.
+  pub(crate) fn write(&self) -> RwLockWriteGuard<'_, Box<dyn Any>> {
+    assert!(
+      self.is_accessible(),
+      "Structure {} can only be accessed from the thread that created it.",
+      self.name
+    );
+
+    match self.value.try_write() {
+      Ok(guard) => guard,
+      Err(TryLockError::Poisoned(error)) => error.into_inner(),
+      Err(TryLockError::WouldBlock) => {
+        panic!("Structure {} is already borrowed.", self.name)
+      }
+    }
+  }
+}
+
+impl Drop for Structure {
+  fn drop(&mut self) {
+    // Structures bound to a thread are leaked rather than dropped elsewhere.
+    if self.is_accessible() {
+      // SAFETY: The value is never used again after this point.
+      unsafe { ManuallyDrop::drop(&mut self.value) }
+    }
+  }
+}
+
+impl std::fmt::Debug for Structure {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Structure")
+      .field("name", &self.name)
+      .field("owner", &self.owner)
+      .field("added", &self.added)
+      .field("changed", &self.changed)
+      .finish()
+  }
+}
+
 /// A container structure that assembles generic structures to compose a state.
 ///
 /// structures can be virtually anything that a structure needs to store as part
@@ -16,9 +216,14 @@ use std::{
 /// This is intended to be used in conjunction with a [`Scheduler`] as input
 /// for the dependencies its schedule' handlers require. Together they allow
 /// the creation of complex and independent systems that can easily co-exist.
+///
+/// The state also holds a change tick, which is incremented whenever handlers
+/// run or structures are added, to find out which structures were added or
+/// changed since a handler last ran.
 #[derive(Debug, Default)]
 pub struct State {
-  structures: HashMap<TypeId, RefCell<Box<dyn Any>>>,
+  structures: Structures,
+  change_tick: AtomicU64,
 }
 
 impl State {
@@ -27,34 +232,94 @@ impl State {
   /// # Arguments
   ///
   /// * `structure` - The structure of type `R` to be added.
-  pub fn add<R: 'static>(&mut self, structure: R) {
-    let key = TypeId::of::<R>();
-    let value = RefCell::new(Box::new(structure));
+  pub fn add<R: Send + Sync + 'static>(&mut self, structure: R) {
+    let tick = self.increment_change_tick();
+
+    self
+      .structures
+      .insert(TypeId::of::<R>(), Structure::new(structure, tick));
+  }
+
+  /// Adds a new generic structure that cannot be shared across threads to the
+  /// state container.
+  ///
+  /// The structure is bound to the current thread. Handlers accessing it will
+  /// always run on that thread, which should be the main thread.
+  ///
+  /// # Arguments
+  ///
+  /// * `structure` - The structure of type `R` to be added.
+  pub fn add_non_send<R: 'static>(&mut self, structure: R) {
+    let tick = self.increment_change_tick();
 
-    self.structures.insert(key, value);
+    self
+      .structures
+      .insert(TypeId::of::<R>(), Structure::new_non_send(structure, tick));
   }
 
-  /// Merges another state into this one.
+  /// Merges another state into this one. Merged structures are considered
+  /// added to this state.
   ///
   /// # Arguments
   ///
   /// * `state` - The [`State`] to be merged into this one.
   pub fn merge(&mut self, state: &mut Self) {
-    for (key, value) in state.drain() {
+    let tick = self.increment_change_tick();
+
+    for (key, mut value) in state.drain() {
+      value.set_added(tick);
       self.structures.insert(key, value);
     }
   }
 
+  /// Returns the current change tick of the state.
+  pub fn change_tick(&self) -> u64 {
+    self.change_tick.load(Ordering::Relaxed)
+  }
+
+  /// Increments the change tick of the state.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The new change tick.
+  pub(crate) fn increment_change_tick(&self) -> u64 {
+    self.change_tick.fetch_add(1, Ordering::Relaxed) + 1
+  }
+
   /// Returns a generic structure from the state container.
   ///
   /// Requested structure must be wrapped with a [`Res`] or [`ResMut`] to get a
   /// read-only reference or one with mutability.
   ///
+  /// Only parameters that do not need any per-handler storage, such as
+  /// [`Local`], can be retrieved this way. As there is no previous run to
+  /// compare with, every structure retrieved this way is considered added and
+  /// changed.
+  ///
   /// # Arguments
   ///
   /// * `->` - A read-only or mutable reference to the requested structure.
-  pub fn get<R: HandlerParam + 'static>(&self) -> <R as HandlerParam>::Item<'_> {
-    R::retrieve(&self.structures)
+  pub fn get<R: HandlerParam<Storage = ()> + 'static>(
+    &self,
+  ) -> <R as HandlerParam>::Item<'_> {
+    let ticks = Ticks::new(0, self.increment_change_tick());
+
+    // Leaking a zero-sized box does not allocate and gives us a unit storage
+    // living as long as the state.
+    R::retrieve(Box::leak(Box::new(())), &self.structures, ticks)
+  }
+
+  /// Removes a generic structure from the state container.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The removed structure, or [`None`] if the state did not contain
+  ///   a structure of type `R`.
+  pub fn remove<R: 'static>(&mut self) -> Option<R> {
+    self
+      .structures
+      .remove(&TypeId::of::<R>())
+      .map(|structure| *structure.into_value().downcast().unwrap())
   }
 
   pub fn has<R: 'static>(&self) -> bool {
@@ -63,13 +328,13 @@ impl State {
 
   /// Returns a read-only reference to all the structures within the state
   /// container.
-  pub fn all(&self) -> &HashMap<TypeId, RefCell<Box<dyn Any>>> {
+  pub fn all(&self) -> &Structures {
     &self.structures
   }
 
   /// Returns a mutable reference to all the structures within the state
   /// container.
-  pub fn all_mut(&mut self) -> &mut HashMap<TypeId, RefCell<Box<dyn Any>>> {
+  pub fn all_mut(&mut self) -> &mut Structures {
     &mut self.structures
   }
 
@@ -82,46 +347,342 @@ impl State {
   /// # Arguments
   ///
   /// * `->` - A vector of tuples containing the type ID and the structure.
-  pub fn drain(&mut self) -> Vec<(TypeId, RefCell<Box<dyn Any>>)> {
+  pub fn drain(&mut self) -> Vec<(TypeId, Structure)> {
     self.structures.drain().collect()
   }
 }
 
+/// A structure describing which structures of a [`State`] a [`Handler`] reads
+/// and writes.
+///
+/// It is used by the [`Scheduler`] to find out which handlers can safely run at
+/// the same time on different threads.
+///
+/// Declaring a mutable access to a structure that is already accessed, or a
+/// read-only access to a structure that is already mutably accessed, records a
+/// conflict as both accesses could not be borrowed at the same time.
+#[derive(Debug, Default, Clone)]
+pub struct Access {
+  reads: HashMap<TypeId, &'static str>,
+  writes: HashMap<TypeId, &'static str>,
+  conflicts: Vec<&'static str>,
+}
+
+impl Access {
+  /// Declares a read-only access to a structure of type `T`.
+  pub fn add_read<T: 'static>(&mut self) {
+    let key = TypeId::of::<T>();
+    let name = std::any::type_name::<T>();
+
+    if self.writes.contains_key(&key) {
+      self.conflicts.push(name);
+    }
+
+    self.reads.insert(key, name);
+  }
+
+  /// Declares a mutable access to a structure of type `T`.
+  pub fn add_write<T: 'static>(&mut self) {
+    let key = TypeId::of::<T>();
+    let name = std::any::type_name::<T>();
+
+    if self.reads.contains_key(&key) || self.writes.contains_key(&key) {
+      self.conflicts.push(name);
+    }
+
+    self.writes.insert(key, name);
+  }
+
+  /// Adds all the accesses of another [`Access`] to this one.
+  ///
+  /// Unlike declaring accesses one by one, this does not record conflicts
+  /// between both accesses. It is meant for accesses that never happen at the
+  /// same time, such as a handler and its run conditions.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The [`Access`] to be merged into this one.
+  pub fn extend(&mut self, other: &Access) {
+    self.reads.extend(other.reads.iter());
+    self.writes.extend(other.writes.iter());
+    self.conflicts.extend(other.conflicts.iter());
+  }
+
+  /// Returns the type names of the structures that were declared with
+  /// conflicting accesses.
+  pub fn conflicts(&self) -> &[&'static str] {
+    &self.conflicts
+  }
+
+  /// Returns whether two accesses can happen at the same time, which is the
+  /// case when neither writes to a structure the other one accesses.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The [`Access`] to compare with.
+  ///
+  /// * `->` - Whether the accesses are compatible.
+  pub fn is_compatible(&self, other: &Access) -> bool {
+    let conflicts = |a: &Access, b: &Access| {
+      a.writes
+        .keys()
+        .any(|key| b.reads.contains_key(key) || b.writes.contains_key(key))
+    };
+
+    !conflicts(self, other) && !conflicts(other, self)
+  }
+
+  /// Returns an iterator over the type IDs of every accessed structure.
+  pub fn types(&self) -> impl Iterator<Item = &TypeId> {
+    self.reads.keys().chain(self.writes.keys())
+  }
+
+  /// Returns an iterator over the type names of the structures read.
+  pub fn reads(&self) -> impl Iterator<Item = &'static str> + '_ {
+    self.reads.values().copied()
+  }
+
+  /// Returns an iterator over the type names of the structures written.
+  pub fn writes(&self) -> impl Iterator<Item = &'static str> + '_ {
+    self.writes.values().copied()
+  }
+}
+
 /// A trait that represents a handler.
 ///
-/// This should only be implemented for [`HandlerFunction`].
-pub trait Handler {
+/// Handlers added to a [`Scheduler`] return either nothing or a [`Result`]
+/// (see [`HandlerOutput`]) while handlers used as run conditions return a
+/// [`bool`], which is why the output is a generic parameter.
+///
+/// This should only be implemented for [`HandlerFunction`] and the
+/// [`crate::conditions`] combinators.
+pub trait Handler<Out = ()>: Send {
   /// Executes the handler.
   ///
   /// # Arguments
   ///
-  /// * `structures` - A mutable reference to a [`HashMap`] of structures.
-  fn run(&mut self, structures: &mut HashMap<TypeId, RefCell<Box<dyn Any>>>);
+  /// * `structures` - A reference to the [`Structures`] of a [`State`].
+  /// * `tick` - The change tick of this run, at which the structures mutated
+  ///   by the handler are marked as changed.
+  ///
+  /// * `->` - The value returned by the handler.
+  fn run(&mut self, structures: &Structures, tick: u64) -> Out;
+
+  /// Declares the structures accessed by the handler.
+  ///
+  /// # Arguments
+  ///
+  /// * `access` - The [`Access`] to add the handler's accesses to.
+  fn access(&self, access: &mut Access);
+
+  /// Returns the name of the handler, used when reporting errors.
+  fn name(&self) -> &'static str {
+    std::any::type_name::<Self>()
+  }
+}
+
+/// A type alias for the error returned by fallible handlers.
+///
+/// Any error type implementing [`std::error::Error`], as well as strings, can
+/// be converted into it, which allows the `?` operator to be used within
+/// handlers.
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A trait for the values handlers added to a [`Scheduler`] can return.
+///
+/// It is implemented for `()` and for `Result<(), E>` where the error can be
+/// converted into a [`BoxedError`]. Errors are reported to the
+/// [`crate::errors::ErrorPolicy`] of the [`State`] once the handler has run.
+pub trait HandlerOutput: 'static {
+  /// Converts the output of a handler into a [`Result`].
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The error returned by the handler, if any.
+  fn into_result(self) -> Result<(), BoxedError>;
+}
+
+impl HandlerOutput for () {
+  fn into_result(self) -> Result<(), BoxedError> {
+    Ok(())
+  }
+}
+
+impl<E: Into<BoxedError> + 'static> HandlerOutput for Result<(), E> {
+  fn into_result(self) -> Result<(), BoxedError> {
+    self.map_err(Into::into)
+  }
+}
+
+/// A [`Handler`] wrapping another one to convert its [`HandlerOutput`] into a
+/// [`Result`], so that handlers with different outputs can share a schedule.
+struct FallibleHandler<H, Out> {
+  handler: H,
+  marker: PhantomData<fn() -> Out>,
+}
+
+impl<H: Handler<Out>, Out: HandlerOutput> Handler<Result<(), BoxedError>>
+  for FallibleHandler<H, Out>
+{
+  fn run(&mut self, structures: &Structures, tick: u64) -> Result<(), BoxedError> {
+    self.handler.run(structures, tick).into_result()
+  }
+
+  fn access(&self, access: &mut Access) {
+    self.handler.access(access);
+  }
+
+  fn name(&self) -> &'static str {
+    self.handler.name()
+  }
+}
+
+/// The change ticks of a handler run, used by [`HandlerParam`]s to find out
+/// which structures were added or changed since the handler last ran.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ticks {
+  last_run: u64,
+  this_run: u64,
+}
+
+impl Ticks {
+  /// Creates new [`Ticks`].
+  ///
+  /// # Arguments
+  ///
+  /// * `last_run` - The change tick of the previous run of the handler.
+  /// * `this_run` - The change tick of the current run of the handler.
+  ///
+  /// * `->` - New [`Ticks`].
+  pub fn new(last_run: u64, this_run: u64) -> Self {
+    Self { last_run, this_run }
+  }
+
+  /// Returns the change tick of the previous run of the handler.
+  pub fn last_run(&self) -> u64 {
+    self.last_run
+  }
+
+  /// Returns the change tick of the current run of the handler.
+  pub fn this_run(&self) -> u64 {
+    self.this_run
+  }
+
+  /// Returns whether something happening at the given change tick happened
+  /// since the previous run of the handler.
+  ///
+  /// # Arguments
+  ///
+  /// * `tick` - The change tick to compare with.
+  pub fn is_newer(&self, tick: u64) -> bool {
+    tick > self.last_run
+  }
 }
 
 /// A trait that represents a valid parameter a generic handler function can
-/// be injected with. By default, two structures implement this:
+/// be injected with. By default, four structures implement this:
 ///
 /// [`Res`]: Access a read-only reference to a structure.
 /// [`ResMut`]: Access a mutable reference to a structure.
+/// [`Local`]: Access data private to the handler.
+/// [`crate::ecs::Query`]: Access the components of entities.
+///
+/// Both can also be wrapped in an [`Option`] for structures that may not be
+/// part of the state when the handler runs.
+///
+/// Parameters can also keep private data between runs of the handler they
+/// belong to through their [`HandlerParam::Storage`], which is how [`Local`]
+/// works. Tuples of parameters are parameters themselves.
 pub trait HandlerParam {
   /// Provides a copy of the struct with a new lifetime.
   type Item<'new>;
 
+  /// The data stored by the parameter within each [`HandlerFunction`] that
+  /// uses it. Parameters that only access structures use `()`.
+  type Storage: Send + 'static;
+
+  /// Creates the storage of the parameter when it is added to a handler.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The initial storage of the parameter.
+  fn init() -> Self::Storage;
+
   /// Retrieves a reference to a structure and wraps it in a new [`Self::Item`].
   ///
   /// # Arguments
   ///
+  /// * `storage` - The storage of the parameter within its handler.
   /// * `structures` - A reference to the injectable structures instance.
+  /// * `ticks` - The change ticks of the handler run.
   ///
   /// * `->` - A reference to the injectable structure.
-  fn retrieve(structures: &HashMap<TypeId, RefCell<Box<dyn Any>>>) -> Self::Item<'_>;
+  fn retrieve<'r>(
+    storage: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r>;
+
+  /// Declares the structures the parameter reads and writes.
+  ///
+  /// # Arguments
+  ///
+  /// * `access` - The [`Access`] of the handler the parameter belongs to.
+  fn access(access: &mut Access);
+}
+
+/// A macro to implement the [`HandlerParam`] trait for tuples of parameters.
+macro_rules! impl_handler_param {
+  ($($p:ident),*) => {
+    #[allow(unused, non_snake_case, clippy::unused_unit)]
+    impl<$($p: HandlerParam),*> HandlerParam for ($($p,)*) {
+      type Item<'new> = ($($p::Item<'new>,)*);
+
+      type Storage = ($($p::Storage,)*);
+
+      fn init() -> Self::Storage {
+        ($($p::init(),)*)
+      }
+
+      fn retrieve<'r>(
+        storage: &'r mut Self::Storage,
+        structures: &'r Structures,
+        ticks: Ticks,
+      ) -> Self::Item<'r> {
+        let ($($p,)*) = storage;
+
+        ($($p::retrieve($p, structures, ticks),)*)
+      }
+
+      fn access(access: &mut Access) {
+        $($p::access(access);)*
+      }
+    }
+  }
 }
 
+impl_handler_param!();
+impl_handler_param!(T1);
+impl_handler_param!(T1, T2);
+impl_handler_param!(T1, T2, T3);
+impl_handler_param!(T1, T2, T3, T4);
+impl_handler_param!(T1, T2, T3, T4, T5);
+impl_handler_param!(T1, T2, T3, T4, T5, T6);
+impl_handler_param!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler_param!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_handler_param!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_handler_param!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+
 /// A structure representing the actual handler function that will be executed
 /// with injected structures.
-pub struct HandlerFunction<Input, F> {
+///
+/// It owns the storage of its parameters so that each handler keeps its own
+/// [`Local`] data, even when the same function is added several times, as well
+/// as the change tick of its last run.
+pub struct HandlerFunction<Input: HandlerParam, F> {
   f: F,
+  storage: Input::Storage,
+  last_run: u64,
   marker: PhantomData<fn() -> Input>,
 }
 
@@ -134,21 +695,32 @@ pub struct HandlerFunction<Input, F> {
 macro_rules! impl_handler {
   ($($p:ident),*) => {
     #[allow(unused, non_snake_case)]
-    impl<F, $($p: HandlerParam),*> Handler for HandlerFunction<($($p,)*), F>
+    impl<F: Send, Out, $($p: HandlerParam),*> Handler<Out> for HandlerFunction<($($p,)*), F>
       where
-        for<'a, 'b> &'a mut F: FnMut($($p),*) +
-          FnMut($(<$p as HandlerParam>::Item<'b>),*)
+        for<'a, 'b> &'a mut F: FnMut($($p),*) -> Out +
+          FnMut($(<$p as HandlerParam>::Item<'b>),*) -> Out
     {
-      fn run(&mut self, structures: &mut HashMap<TypeId, RefCell<Box<dyn Any>>>) {
+      fn run(&mut self, structures: &Structures, tick: u64) -> Out {
         #[allow(clippy::too_many_arguments)]
-        fn call_inner<$($p),*>(mut f: impl FnMut($($p),*), $($p: $p),*) {
+        fn call_inner<Out, $($p),*>(mut f: impl FnMut($($p),*) -> Out, $($p: $p),*) -> Out {
           f($($p),*)
         }
 
-        $(let $p= $p::retrieve(structures);)*
+        let ticks = Ticks::new(self.last_run, tick);
+        self.last_run = tick;
+
+        let ($($p,)*) = <($($p,)*)>::retrieve(&mut self.storage, structures, ticks);
 
         call_inner(&mut self.f, $($p),*)
       }
+
+      fn access(&self, access: &mut Access) {
+        $($p::access(access);)*
+      }
+
+      fn name(&self) -> &'static str {
+        std::any::type_name::<F>()
+      }
     }
   }
 }
@@ -168,8 +740,8 @@ impl_handler!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
 
 /// A trait that represents a generic function that can be transformed into a
 /// [`Handler`] that can inject parameters into it.
-pub trait IntoHandler<Input> {
-  type Handler: Handler;
+pub trait IntoHandler<Input, Out = ()> {
+  type Handler: Handler<Out>;
 
   /// Transforms the generic function into a [`Handler`].
   ///
@@ -184,16 +756,18 @@ pub trait IntoHandler<Input> {
 /// [`HandlerFunction`] to be injected with dependencies.
 macro_rules! impl_into_handler{
   ($($p:ident),*) => {
-    impl<F, $($p: HandlerParam),*> IntoHandler<($($p,)*)> for F
+    impl<F: Send, Out, $($p: HandlerParam),*> IntoHandler<($($p,)*), Out> for F
       where
-        for<'a, 'b> &'a mut F: FnMut($($p),*) +
-          FnMut($(<$p as HandlerParam>::Item<'b>),*)
+        for<'a, 'b> &'a mut F: FnMut($($p),*) -> Out +
+          FnMut($(<$p as HandlerParam>::Item<'b>),*) -> Out
     {
       type Handler = HandlerFunction<($($p,)*), Self>;
 
       fn into_handler(self) -> Self::Handler {
         HandlerFunction {
           f: self,
+          storage: <($($p,)*)>::init(),
+          last_run: 0,
           marker: Default::default(),
         }
       }
@@ -216,10 +790,44 @@ impl_into_handler!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
 
 /// A struct that represents a read-only reference to a structure.
 pub struct Res<'a, T: 'static> {
-  value: Ref<'a, Box<dyn Any>>,
+  value: RwLockReadGuard<'a, Box<dyn Any>>,
+  added: u64,
+  changed: u64,
+  ticks: Ticks,
   _marker: PhantomData<&'a T>,
 }
 
+impl<'a, T: 'static> Res<'a, T> {
+  /// Creates a new [`Res`] borrowing a structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `structure` - The [`Structure`] to borrow.
+  /// * `ticks` - The change ticks of the handler run.
+  ///
+  /// * `->` - A new [`Res`].
+  pub(crate) fn new(structure: &'a Structure, ticks: Ticks) -> Self {
+    Self {
+      value: structure.read(),
+      added: structure.added(),
+      changed: structure.changed(),
+      ticks,
+      _marker: PhantomData,
+    }
+  }
+
+  /// Returns whether the structure was added since the handler last ran.
+  pub fn is_added(&self) -> bool {
+    self.ticks.is_newer(self.added)
+  }
+
+  /// Returns whether the structure was added or mutably dereferenced since the
+  /// handler last ran.
+  pub fn is_changed(&self) -> bool {
+    self.ticks.is_newer(self.changed)
+  }
+}
+
 /// Allow access to a read-only reference to the underlying structure.
 impl<T: 'static> Deref for Res<'_, T> {
   type Target = T;
@@ -233,23 +841,104 @@ impl<'res, T: 'static> HandlerParam for Res<'res, T> {
   /// Provides a copy of the struct with a new lifetime.
   type Item<'new> = Res<'new, T>;
 
-  fn retrieve(structures: &HashMap<TypeId, RefCell<Box<dyn Any>>>) -> Self::Item<'_> {
-    Res {
-      value: structures
+  type Storage = ();
+
+  fn init() -> Self::Storage {}
+
+  fn retrieve<'r>(
+    _: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r> {
+    Res::new(
+      structures
         .get(&TypeId::of::<T>())
-        .expect("Cannot find structure.")
-        .borrow(),
-      _marker: PhantomData,
-    }
+        .expect("Cannot find structure."),
+      ticks,
+    )
+  }
+
+  fn access(access: &mut Access) {
+    access.add_read::<T>();
+  }
+}
+
+/// Allow handlers to request a structure that may not exist in the state yet.
+/// Resolves to [`None`] instead of panicking when the structure is missing.
+impl<'res, T: 'static> HandlerParam for Option<Res<'res, T>> {
+  /// Provides a copy of the struct with a new lifetime.
+  type Item<'new> = Option<Res<'new, T>>;
+
+  type Storage = ();
+
+  fn init() -> Self::Storage {}
+
+  fn retrieve<'r>(
+    _: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r> {
+    structures
+      .get(&TypeId::of::<T>())
+      .map(|structure| Res::new(structure, ticks))
+  }
+
+  fn access(access: &mut Access) {
+    access.add_read::<T>();
   }
 }
 
 /// A struct that represents a mutable reference to a structure.
+///
+/// Mutably dereferencing it marks the structure as changed.
 pub struct ResMut<'a, T: 'static> {
-  value: RefMut<'a, Box<dyn Any>>,
+  value: RwLockWriteGuard<'a, Box<dyn Any>>,
+  added: u64,
+  changed: &'a AtomicU64,
+  ticks: Ticks,
   _marker: PhantomData<&'a mut T>,
 }
 
+impl<'a, T: 'static> ResMut<'a, T> {
+  /// Creates a new [`ResMut`] mutably borrowing a structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `structure` - The [`Structure`] to borrow.
+  /// * `ticks` - The change ticks of the handler run.
+  ///
+  /// * `->` - A new [`ResMut`].
+  pub(crate) fn new(structure: &'a Structure, ticks: Ticks) -> Self {
+    Self {
+      value: structure.write(),
+      added: structure.added(),
+      changed: &structure.changed,
+      ticks,
+      _marker: PhantomData,
+    }
+  }
+
+  /// Returns whether the structure was added since the handler last ran.
+  pub fn is_added(&self) -> bool {
+    self.ticks.is_newer(self.added)
+  }
+
+  /// Returns whether the structure was added or mutably dereferenced since the
+  /// handler last ran, including by this handler during this run.
+  pub fn is_changed(&self) -> bool {
+    self.ticks.is_newer(self.changed.load(Ordering::Relaxed))
+  }
+
+  /// Returns a mutable reference to the underlying structure without marking
+  /// it as changed.
+  ///
+  /// Useful for bookkeeping that other handlers should not react to, such as
+  /// uploading the structure to the GPU once it has changed.
+  pub fn bypass_change_detection(&mut self) -> &mut T {
+    self.value.downcast_mut().unwrap()
+  }
+}
+
 /// Allow access to a read-only reference to the underlying structure.
 impl<T: 'static> Deref for ResMut<'_, T> {
   type Target = T;
@@ -262,7 +951,8 @@ impl<T: 'static> Deref for ResMut<'_, T> {
 /// Allow access to a mutable reference to the underlying structure.
 impl<T: 'static> DerefMut for ResMut<'_, T> {
   fn deref_mut(&mut self) -> &mut T {
-    self.value.downcast_mut().unwrap()
+    self.changed.store(self.ticks.this_run(), Ordering::Relaxed);
+    self.bypass_change_detection()
   }
 }
 
@@ -270,13 +960,566 @@ impl<'res, T: 'static> HandlerParam for ResMut<'res, T> {
   /// Provides a copy of the struct with a new lifetime.
   type Item<'new> = ResMut<'new, T>;
 
-  fn retrieve(structures: &HashMap<TypeId, RefCell<Box<dyn Any>>>) -> Self::Item<'_> {
-    ResMut {
-      value: structures.get(&TypeId::of::<T>()).unwrap().borrow_mut(),
-      _marker: PhantomData,
-    }
-  }
-}
+  type Storage = ();
+
+  fn init() -> Self::Storage {}
+
+  fn retrieve<'r>(
+    _: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r> {
+    ResMut::new(structures.get(&TypeId::of::<T>()).unwrap(), ticks)
+  }
+
+  fn access(access: &mut Access) {
+    access.add_write::<T>();
+  }
+}
+
+/// Allow handlers to request a mutable structure that may not exist in the
+/// state yet. Resolves to [`None`] instead of panicking when the structure is
+/// missing.
+impl<'res, T: 'static> HandlerParam for Option<ResMut<'res, T>> {
+  /// Provides a copy of the struct with a new lifetime.
+  type Item<'new> = Option<ResMut<'new, T>>;
+
+  type Storage = ();
+
+  fn init() -> Self::Storage {}
+
+  fn retrieve<'r>(
+    _: &'r mut Self::Storage,
+    structures: &'r Structures,
+    ticks: Ticks,
+  ) -> Self::Item<'r> {
+    structures
+      .get(&TypeId::of::<T>())
+      .map(|structure| ResMut::new(structure, ticks))
+  }
+
+  fn access(access: &mut Access) {
+    access.add_write::<T>();
+  }
+}
+
+/// A struct that represents data private to a handler which persists between
+/// its runs.
+///
+/// The data is created from its [`Default`] value when the handler is added to
+/// a [`Scheduler`] and lives within the handler itself rather than the
+/// [`State`]. Adding the same function twice creates two independent values.
+pub struct Local<'a, T: Default + Send + 'static> {
+  value: &'a mut T,
+}
+
+/// Allow access to a read-only reference to the underlying data.
+impl<T: Default + Send + 'static> Deref for Local<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.value
+  }
+}
+
+/// Allow access to a mutable reference to the underlying data.
+impl<T: Default + Send + 'static> DerefMut for Local<'_, T> {
+  fn deref_mut(&mut self) -> &mut T {
+    self.value
+  }
+}
+
+impl<'local, T: Default + Send + 'static> HandlerParam for Local<'local, T> {
+  /// Provides a copy of the struct with a new lifetime.
+  type Item<'new> = Local<'new, T>;
+
+  type Storage = T;
+
+  fn init() -> Self::Storage {
+    T::default()
+  }
+
+  fn retrieve<'r>(
+    storage: &'r mut Self::Storage,
+    _: &'r Structures,
+    _: Ticks,
+  ) -> Self::Item<'r> {
+    Local { value: storage }
+  }
+
+  fn access(_: &mut Access) {}
+}
+
+/// A type alias for the function of an exclusive handler. See
+/// [`IntoHandlerConfig`] for the functions that can be used as such.
+type ExclusiveFn =
+  Box<dyn FnMut(&mut State, &mut Scheduler) -> Result<(), BoxedError> + Send>;
+
+/// The kinds of handlers a [`Schedule`] can run.
+enum HandlerKind {
+  /// A [`Handler`] injected with structures, which may run alongside others.
+  Shared(Box<dyn Handler<Result<(), BoxedError>>>),
+
+  /// A function with direct access to the [`State`] and [`Scheduler`], which
+  /// always runs alone.
+  Exclusive(ExclusiveFn),
+}
+
+/// A unique identifier of a handler added to a [`Scheduler`], used to remove,
+/// disable or enable it later on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+impl HandlerId {
+  /// Returns a new identifier, different from every identifier returned so far.
+  fn next() -> Self {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    Self(NEXT.fetch_add(1, Ordering::Relaxed))
+  }
+}
+
+/// A change to a handler that has already been added to a [`Scheduler`].
+#[derive(Clone, Copy)]
+enum HandlerChange {
+  Remove,
+  Enable(bool),
+}
+
+/// A structure describing how a [`Handler`] should be added to a [`Schedule`].
+///
+/// Besides the handler itself, it carries the names under which the handler can
+/// be referred to by other handlers as well as its ordering constraints. Names
+/// can either be a unique label or a set shared by several handlers, both of
+/// which can be used as targets of [`IntoHandlerConfig::before`] and
+/// [`IntoHandlerConfig::after`].
+pub struct HandlerConfig {
+  id: HandlerId,
+  name: &'static str,
+  enabled: bool,
+  handler: HandlerKind,
+  conditions: Vec<Box<dyn Handler<bool>>>,
+  access: Access,
+  labels: Vec<&'static str>,
+  sets: Vec<&'static str>,
+  before: Vec<&'static str>,
+  after: Vec<&'static str>,
+}
+
+impl HandlerConfig {
+  /// Creates a new [`HandlerConfig`] without any ordering constraints.
+  ///
+  /// # Arguments
+  ///
+  /// * `handler` - The [`Handler`] to be configured.
+  ///
+  /// * `->` - A new [`HandlerConfig`] wrapping the handler.
+  pub fn new<Out: HandlerOutput>(handler: impl Handler<Out> + 'static) -> Self {
+    let mut access = Access::default();
+    handler.access(&mut access);
+
+    let name = handler.name();
+    let handler = HandlerKind::Shared(Box::new(FallibleHandler {
+      handler,
+      marker: PhantomData,
+    }));
+
+    Self::with_kind(name, handler, access)
+  }
+
+  /// Creates a new [`HandlerConfig`] for an exclusive handler.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the handler.
+  /// * `handler` - The function of the exclusive handler.
+  ///
+  /// * `->` - A new [`HandlerConfig`] wrapping the handler.
+  fn exclusive(name: &'static str, handler: ExclusiveFn) -> Self {
+    Self::with_kind(name, HandlerKind::Exclusive(handler), Access::default())
+  }
+
+  /// Creates a new [`HandlerConfig`] from any kind of handler.
+  fn with_kind(name: &'static str, handler: HandlerKind, access: Access) -> Self {
+    Self {
+      id: HandlerId::next(),
+      name,
+      enabled: true,
+      handler,
+      conditions: Vec::new(),
+      access,
+      labels: Vec::new(),
+      sets: Vec::new(),
+      before: Vec::new(),
+      after: Vec::new(),
+    }
+  }
+
+  /// Returns whether all of the run conditions of the handler are met.
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the [`Structures`] of a [`State`].
+  /// * `tick` - The change tick of this run.
+  fn should_run(&mut self, structures: &Structures, tick: u64) -> bool {
+    self.enabled
+      && self
+        .conditions
+        .iter_mut()
+        .all(|condition| condition.run(structures, tick))
+  }
+
+  /// Returns the identifier of the handler.
+  pub fn id(&self) -> HandlerId {
+    self.id
+  }
+
+  /// Executes the handler if all of its run conditions are met.
+  ///
+  /// Exclusive handlers cannot run this way as they need the whole [`State`].
+  /// See [`HandlerConfig::run_alone`].
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the [`Structures`] of a [`State`].
+  /// * `tick` - The change tick of this run.
+  ///
+  /// * `->` - The error returned by the handler, if any.
+  fn run(&mut self, structures: &Structures, tick: u64) -> Result<(), HandlerError> {
+    if !self.should_run(structures, tick) {
+      return Ok(());
+    }
+
+    let result = match &mut self.handler {
+      HandlerKind::Shared(handler) => handler.run(structures, tick),
+      HandlerKind::Exclusive(_) => unreachable!("Exclusive handlers run alone."),
+    };
+
+    result.map_err(|error| HandlerError::new(self.name, error))
+  }
+
+  /// Executes the handler, of any kind, if all of its run conditions are met.
+  /// The handler must be the only one running at that time.
+  ///
+  /// # Arguments
+  ///
+  /// * `state` - A mutable reference to the [`State`].
+  /// * `scheduler` - A mutable reference to the [`Scheduler`] running the
+  ///   handler.
+  /// * `tick` - The change tick of this run.
+  ///
+  /// * `->` - The error returned by the handler, if any.
+  fn run_alone(
+    &mut self,
+    state: &mut State,
+    scheduler: &mut Scheduler,
+    tick: u64,
+  ) -> Result<(), HandlerError> {
+    if !self.should_run(state.all(), tick) {
+      return Ok(());
+    }
+
+    let result = match &mut self.handler {
+      HandlerKind::Shared(handler) => handler.run(state.all(), tick),
+      HandlerKind::Exclusive(handler) => handler(state, scheduler),
+    };
+
+    result.map_err(|error| HandlerError::new(self.name, error))
+  }
+
+  /// Returns whether the handler is exclusive and must run alone.
+  fn is_exclusive(&self) -> bool {
+    matches!(self.handler, HandlerKind::Exclusive(_))
+  }
+
+  /// Returns whether the handler accesses a structure that is bound to the
+  /// thread that created it, in which case it must run on that thread.
+  ///
+  /// # Arguments
+  ///
+  /// * `structures` - A reference to the [`Structures`] of a [`State`].
+  fn is_main_thread(&self, structures: &Structures) -> bool {
+    self
+      .access
+      .types()
+      .any(|key| structures.get(key).is_some_and(|value| !value.is_send()))
+  }
+
+  /// Returns whether the handler can be referred to by the given name, either
+  /// through one of its labels or one of its sets.
+  fn is_named(&self, name: &str) -> bool {
+    self.labels.contains(&name) || self.sets.contains(&name)
+  }
+
+  /// Returns a human readable description of the handler used when reporting
+  /// scheduling errors.
+  fn describe(&self) -> String {
+    match self.labels.first() {
+      Some(label) => format!("\"{}\"", label),
+      None => self.name.to_string(),
+    }
+  }
+}
+
+/// A trait for anything that can be added to a [`Schedule`] as a handler.
+///
+/// It is implemented for any [`IntoHandler`] function returning a
+/// [`HandlerOutput`] as well as for [`HandlerConfig`] itself, which allows chaining the configuration methods
+/// directly on a function when adding it to a [`Scheduler`].
+pub trait IntoHandlerConfig<Input> {
+  /// Transforms the value into a [`HandlerConfig`].
+  fn into_config(self) -> HandlerConfig;
+
+  /// Gives the handler a label other handlers can be ordered against.
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The name of the label.
+  ///
+  /// * `->` - The updated [`HandlerConfig`].
+  fn label(self, label: &'static str) -> HandlerConfig
+  where
+    Self: Sized,
+  {
+    let mut config = self.into_config();
+    config.labels.push(label);
+
+    config
+  }
+
+  /// Adds the handler to a named set. Ordering constraints targeting the set
+  /// apply to every handler within it.
+  ///
+  /// # Arguments
+  ///
+  /// * `set` - The name of the set.
+  ///
+  /// * `->` - The updated [`HandlerConfig`].
+  fn in_set(self, set: &'static str) -> HandlerConfig
+  where
+    Self: Sized,
+  {
+    let mut config = self.into_config();
+    config.sets.push(set);
+
+    config
+  }
+
+  /// Requires the handler to run before every handler with the given label or
+  /// within the given set.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The label or set that should run after this handler.
+  ///
+  /// * `->` - The updated [`HandlerConfig`].
+  fn before(self, name: &'static str) -> HandlerConfig
+  where
+    Self: Sized,
+  {
+    let mut config = self.into_config();
+    config.before.push(name);
+
+    config
+  }
+
+  /// Requires the handler to run after every handler with the given label or
+  /// within the given set.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The label or set that should run before this handler.
+  ///
+  /// * `->` - The updated [`HandlerConfig`].
+  fn after(self, name: &'static str) -> HandlerConfig
+  where
+    Self: Sized,
+  {
+    let mut config = self.into_config();
+    config.after.push(name);
+
+    config
+  }
+
+  /// Only runs the handler when the given condition returns `true`. When
+  /// called several times, all conditions must be met for the handler to run.
+  ///
+  /// Conditions are handler functions themselves and can be injected with any
+  /// [`HandlerParam`]. See [`crate::conditions`] for built-in conditions and
+  /// combinators.
+  ///
+  /// # Arguments
+  ///
+  /// * `condition` - The condition deciding whether the handler should run.
+  ///
+  /// * `->` - The updated [`HandlerConfig`].
+  fn run_if<C, H>(
+    self,
+    condition: impl IntoHandler<C, bool, Handler = H>,
+  ) -> HandlerConfig
+  where
+    Self: Sized,
+    H: Handler<bool> + 'static,
+  {
+    let mut config = self.into_config();
+    let condition = condition.into_handler();
+
+    // Conditions run before the handler so their accesses cannot conflict.
+    let mut access = Access::default();
+    condition.access(&mut access);
+
+    config.access.extend(&access);
+    config.conditions.push(Box::new(condition));
+
+    config
+  }
+}
+
+impl<I, Out, F> IntoHandlerConfig<(I, Out)> for F
+where
+  F: IntoHandler<I, Out>,
+  F::Handler: 'static,
+  Out: HandlerOutput,
+{
+  fn into_config(self) -> HandlerConfig {
+    HandlerConfig::new(self.into_handler())
+  }
+}
+
+impl IntoHandlerConfig<HandlerConfig> for HandlerConfig {
+  fn into_config(self) -> HandlerConfig {
+    self
+  }
+}
+
+/// Allow functions receiving a mutable reference to the whole [`State`] to be
+/// added as exclusive handlers. Exclusive handlers always run alone, after
+/// every handler before them has completed and before any handler after them
+/// has started.
+impl<Out, F> IntoHandlerConfig<fn(&mut State) -> Out> for F
+where
+  F: FnMut(&mut State) -> Out + Send + 'static,
+  Out: HandlerOutput,
+{
+  fn into_config(mut self) -> HandlerConfig {
+    HandlerConfig::exclusive(
+      std::any::type_name::<F>(),
+      Box::new(move |state, _| self(state).into_result()),
+    )
+  }
+}
+
+/// Allow exclusive handlers to also receive the [`Scheduler`] running them.
+///
+/// Handlers added to the schedule that is currently running are only taken into
+/// account from its next run.
+impl<Out, F> IntoHandlerConfig<fn(&mut State, &mut Scheduler) -> Out> for F
+where
+  F: FnMut(&mut State, &mut Scheduler) -> Out + Send + 'static,
+  Out: HandlerOutput,
+{
+  fn into_config(mut self) -> HandlerConfig {
+    HandlerConfig::exclusive(
+      std::any::type_name::<F>(),
+      Box::new(move |state, scheduler| self(state, scheduler).into_result()),
+    )
+  }
+}
+
+/// A structure describing the ordering constraints of a named handler set.
+///
+/// Constraints declared on a set apply to every handler that was added to it
+/// through [`IntoHandlerConfig::in_set`].
+pub struct HandlerSet {
+  name: &'static str,
+  before: Vec<&'static str>,
+  after: Vec<&'static str>,
+}
+
+impl HandlerSet {
+  /// Creates a new [`HandlerSet`] configuration without any constraints.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the set.
+  ///
+  /// * `->` - A new [`HandlerSet`].
+  pub fn new(name: &'static str) -> Self {
+    Self {
+      name,
+      before: Vec::new(),
+      after: Vec::new(),
+    }
+  }
+
+  /// Requires the set to run before every handler with the given label or
+  /// within the given set.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The label or set that should run after this set.
+  ///
+  /// * `->` - The updated [`HandlerSet`].
+  pub fn before(mut self, name: &'static str) -> Self {
+    self.before.push(name);
+
+    self
+  }
+
+  /// Requires the set to run after every handler with the given label or
+  /// within the given set.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The label or set that should run before this set.
+  ///
+  /// * `->` - The updated [`HandlerSet`].
+  pub fn after(mut self, name: &'static str) -> Self {
+    self.after.push(name);
+
+    self
+  }
+}
+
+/// An error raised when a [`Schedule`] cannot be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+  /// The ordering constraints contain a cycle. Holds the descriptions of the
+  /// handlers forming the cycle, in order.
+  Cycle(Vec<String>),
+
+  /// A handler requests access to a structure that conflicts with another of
+  /// its own accesses, such as a [`Res`] and a [`ResMut`] of the same type.
+  ConflictingAccess {
+    /// The description of the handler.
+    handler: String,
+
+    /// The type name of the structure.
+    structure: &'static str,
+  },
+}
+
+impl std::fmt::Display for ScheduleError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ScheduleError::Cycle(handlers) => write!(
+        f,
+        "Handler ordering contains a cycle: {} -> {}",
+        handlers.join(" -> "),
+        handlers[0]
+      ),
+      ScheduleError::ConflictingAccess { handler, structure } => write!(
+        f,
+        "Handler {} has conflicting accesses to structure {}. A structure \
+         cannot be requested mutably more than once or both mutably and \
+         immutably by the same handler.",
+        handler, structure
+      ),
+    }
+  }
+}
+
+impl std::error::Error for ScheduleError {}
 
 /// A structure that allows the storage of [`Handler`] functions to be executed
 /// with dynamically injected structures.
@@ -288,9 +1531,25 @@ impl<'res, T: 'static> HandlerParam for ResMut<'res, T> {
 /// [`Schedule`]s are agnostic of the [`State`] used to execute the [`Handler`]
 /// functions. This is why any function that executes a [`Handler`]s will
 /// require a [`State`] to be specified.
+///
+/// Handlers run in insertion order unless ordering constraints say otherwise.
+/// The order is resolved lazily the first time the schedule runs after being
+/// modified.
+///
+/// Consecutive handlers whose [`Access`]es do not conflict are grouped into
+/// batches that run concurrently on a thread pool. Handlers accessing
+/// structures bound to a thread always run on the thread running the schedule.
+/// Exclusive handlers, which receive the whole [`State`], always run alone.
+///
+/// Errors returned by fallible handlers are reported to the
+/// [`crate::errors::ErrorPolicy`] of the [`State`] once the batch they ran in
+/// has completed.
 #[derive(Default)]
 pub(crate) struct Schedule {
-  handlers: Vec<Box<dyn Handler>>,
+  handlers: Vec<HandlerConfig>,
+  sets: Vec<HandlerSet>,
+  batches: Vec<Vec<usize>>,
+  dirty: bool,
 }
 
 impl Schedule {
@@ -300,59 +1559,558 @@ impl Schedule {
   /// # Arguments
   ///
   /// * `state` - A mutable reference to a [`State`].
-  pub fn run(&mut self, state: &mut State) {
-    let structures = state.all_mut();
+  /// * `scheduler` - The [`Scheduler`] the schedule was taken out of, handed
+  ///   to exclusive handlers.
+  /// * `recorder` - The [`Recorder`] timing the handlers, if profiling.
+  pub fn run(
+    &mut self,
+    state: &mut State,
+    scheduler: &mut Scheduler,
+    recorder: Option<&Recorder>,
+  ) {
+    if let Err(error) = self.build() {
+      panic!("{}", error);
+    }
+
+    // Run the batches in order. Handlers of a batch never access the same
+    // structure mutably, so they can share a change tick.
+    for batch in self.batches.iter() {
+      let tick = state.increment_change_tick();
+
+      if let [index] = batch[..] {
+        let handler = &mut self.handlers[index];
+        let result = Recorder::time(recorder, handler.name, || {
+          handler.run_alone(state, scheduler, tick)
+        });
+
+        if let Err(error) = result {
+          errors::report(state.all(), error, tick);
+        }
+
+        continue;
+      }
+
+      let structures = state.all();
+
+      let handlers = self
+        .handlers
+        .iter_mut()
+        .enumerate()
+        .filter(|(index, _)| batch.contains(index))
+        .map(|(_, handler)| handler);
 
-    // Run the handlers in order.
-    for handler in self.handlers.iter_mut() {
-      handler.run(structures);
+      // Keep the handlers bound to this thread and send the others to the pool.
+      let (mut main_thread, mut pool): (Vec<_>, Vec<_>) =
+        handlers.partition(|handler| handler.is_main_thread(structures));
+
+      // Make this thread useful while waiting for the pool.
+      if main_thread.is_empty() {
+        main_thread.extend(pool.pop());
+      }
+
+      let failures = Mutex::new(Vec::new());
+
+      rayon::in_place_scope(|scope| {
+        for handler in pool {
+          let failures = &failures;
+
+          scope.spawn(move |_| {
+            let result =
+              Recorder::time(recorder, handler.name, || handler.run(structures, tick));
+
+            if let Err(error) = result {
+              failures.lock().unwrap().push(error);
+            }
+          });
+        }
+
+        for handler in main_thread {
+          let result =
+            Recorder::time(recorder, handler.name, || handler.run(structures, tick));
+
+          if let Err(error) = result {
+            failures.lock().unwrap().push(error);
+          }
+        }
+      });
+
+      // Report errors once no handler holds a structure anymore.
+      for error in failures.into_inner().unwrap() {
+        errors::report(structures, error, tick);
+      }
     }
   }
 
-  /// Adds a new [`Handler`] to the [`Schedule`].
+  /// Adds a new [`Handler`] to the [`Schedule`] after validating its accesses.
   ///
   /// # Arguments
   ///
-  /// * `handler` - The [`Handler`] to be added.
-  pub fn add_handler<I, S: Handler + 'static>(
+  /// * `handler` - The [`Handler`] to be added along with its configuration.
+  ///
+  /// * `->` - The identifier of the added handler, or an error if the
+  ///   handler's accesses conflict with each other.
+  pub fn add_handler<I>(
     &mut self,
-    handler: impl IntoHandler<I, Handler = S>,
-  ) {
-    self.handlers.push(Box::new(handler.into_handler()));
+    handler: impl IntoHandlerConfig<I>,
+  ) -> Result<HandlerId, ScheduleError> {
+    let handler = handler.into_config();
+    let id = handler.id;
+
+    if let Some(structure) = handler.access.conflicts().first() {
+      return Err(ScheduleError::ConflictingAccess {
+        handler: handler.describe(),
+        structure,
+      });
+    }
+
+    self.handlers.push(handler);
+    self.dirty = true;
+
+    Ok(id)
+  }
+
+  /// Adds or extends the ordering constraints of a named handler set.
+  ///
+  /// # Arguments
+  ///
+  /// * `set` - The [`HandlerSet`] configuration.
+  pub fn configure_set(&mut self, set: HandlerSet) {
+    self.sets.push(set);
+    self.dirty = true;
+  }
+
+  /// Applies a change to one of the handlers of the [`Schedule`].
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler.
+  /// * `change` - The change to apply.
+  ///
+  /// * `->` - Whether the schedule contains the handler.
+  fn change_handler(&mut self, id: HandlerId, change: HandlerChange) -> bool {
+    let Some(index) = self.handlers.iter().position(|handler| handler.id == id) else {
+      return false;
+    };
+
+    match change {
+      HandlerChange::Remove => {
+        self.handlers.remove(index);
+        self.dirty = true;
+      }
+      HandlerChange::Enable(enabled) => self.handlers[index].enabled = enabled,
+    }
+
+    true
+  }
+
+  /// Moves the handlers and sets of another [`Schedule`] into this one.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The [`Schedule`] to be merged.
+  fn merge(&mut self, other: Schedule) {
+    self.handlers.extend(other.handlers);
+    self.sets.extend(other.sets);
+    self.dirty = true;
+  }
+
+  /// Resolves the execution order of the handlers from their ordering
+  /// constraints. Handlers that are not constrained relative to each other keep
+  /// their insertion order.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - An error if the constraints cannot be satisfied.
+  pub fn build(&mut self) -> Result<(), ScheduleError> {
+    if !self.dirty {
+      return Ok(());
+    }
+
+    let edges = self.edges();
+    let mut in_degrees = vec![0; self.handlers.len()];
+
+    for &to in edges.iter().flatten() {
+      in_degrees[to] += 1;
+    }
+
+    // Kahn's algorithm, always picking the earliest inserted ready handler.
+    let mut ready: std::collections::BTreeSet<usize> = (0..self.handlers.len())
+      .filter(|&i| in_degrees[i] == 0)
+      .collect();
+    let mut order = Vec::with_capacity(self.handlers.len());
+
+    while let Some(index) = ready.pop_first() {
+      order.push(index);
+
+      for &to in edges[index].iter() {
+        in_degrees[to] -= 1;
+
+        if in_degrees[to] == 0 {
+          ready.insert(to);
+        }
+      }
+    }
+
+    if order.len() < self.handlers.len() {
+      let cycle = self.find_cycle(&edges, &in_degrees);
+
+      return Err(ScheduleError::Cycle(
+        cycle.iter().map(|&i| self.handlers[i].describe()).collect(),
+      ));
+    }
+
+    self.batches = self.batches(&order, &edges);
+    self.dirty = false;
+
+    Ok(())
+  }
+
+  /// Groups consecutive handlers of the execution order into batches that can
+  /// run concurrently. A new batch is started whenever a handler conflicts with,
+  /// or must run after, a handler of the current batch. Exclusive handlers
+  /// always get a batch of their own.
+  ///
+  /// # Arguments
+  ///
+  /// * `order` - The resolved execution order of the handlers.
+  /// * `edges` - The adjacency list of the ordering constraints.
+  ///
+  /// * `->` - The batches of handler indices, in execution order.
+  fn batches(&self, order: &[usize], edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut batches = Vec::new();
+    let mut batch: Vec<usize> = Vec::new();
+    let mut access = Access::default();
+
+    for &index in order {
+      let handler = &self.handlers[index];
+      let is_ordered = batch.iter().any(|&other| edges[other].contains(&index));
+      let is_exclusive = handler.is_exclusive()
+        || batch
+          .iter()
+          .any(|&other| self.handlers[other].is_exclusive());
+
+      if !batch.is_empty()
+        && (is_ordered || is_exclusive || !access.is_compatible(&handler.access))
+      {
+        batches.push(std::mem::take(&mut batch));
+        access = Access::default();
+      }
+
+      batch.push(index);
+      access.extend(&handler.access);
+    }
+
+    if !batch.is_empty() {
+      batches.push(batch);
+    }
+
+    batches
+  }
+
+  /// Describes the handlers of the schedule in the order they run. The
+  /// schedule must have been built beforehand.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the label of the schedule.
+  ///
+  /// * `->` - The description of the schedule.
+  fn describe(&self, name: &str) -> ScheduleInfo {
+    let edges = self.edges();
+    let order: Vec<usize> = self.batches.iter().flatten().copied().collect();
+    let position = |index: usize| order.iter().position(|&i| i == index).unwrap();
+
+    let mut handlers = Vec::with_capacity(order.len());
+
+    for (batch, indices) in self.batches.iter().enumerate() {
+      for &index in indices {
+        let handler = &self.handlers[index];
+
+        let mut dependencies: Vec<usize> = (0..edges.len())
+          .filter(|&from| edges[from].contains(&index))
+          .map(position)
+          .collect();
+        dependencies.sort_unstable();
+
+        let mut reads: Vec<_> = handler.access.reads().collect();
+        let mut writes: Vec<_> = handler.access.writes().collect();
+        reads.sort_unstable();
+        writes.sort_unstable();
+
+        handlers.push(HandlerInfo {
+          name: handler.name,
+          labels: handler.labels.clone(),
+          sets: handler.sets.clone(),
+          reads,
+          writes,
+          exclusive: handler.is_exclusive(),
+          batch,
+          dependencies,
+        });
+      }
+    }
+
+    ScheduleInfo {
+      name: name.to_string(),
+      handlers,
+    }
+  }
+
+  /// Builds the adjacency list of the ordering constraints where an edge from
+  /// `a` to `b` means that `a` must run before `b`.
+  fn edges(&self) -> Vec<Vec<usize>> {
+    let mut edges = vec![Vec::new(); self.handlers.len()];
+
+    let named = |name: &str| {
+      self
+        .handlers
+        .iter()
+        .enumerate()
+        .filter(move |(_, handler)| handler.is_named(name))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>()
+    };
+
+    let mut add_edge = |from: usize, to: usize| {
+      if from != to && !edges[from].contains(&to) {
+        edges[from].push(to);
+      }
+    };
+
+    for (index, handler) in self.handlers.iter().enumerate() {
+      for &name in handler.before.iter() {
+        named(name).into_iter().for_each(|to| add_edge(index, to));
+      }
+
+      for &name in handler.after.iter() {
+        named(name)
+          .into_iter()
+          .for_each(|from| add_edge(from, index));
+      }
+    }
+
+    for set in self.sets.iter() {
+      for member in named(set.name) {
+        for &name in set.before.iter() {
+          named(name).into_iter().for_each(|to| add_edge(member, to));
+        }
+
+        for &name in set.after.iter() {
+          named(name)
+            .into_iter()
+            .for_each(|from| add_edge(from, member));
+        }
+      }
+    }
+
+    edges
+  }
+
+  /// Finds a cycle among the handlers that could not be sorted.
+  ///
+  /// # Arguments
+  ///
+  /// * `edges` - The adjacency list of the ordering constraints.
+  /// * `in_degrees` - The remaining in-degrees after sorting. Handlers with a
+  ///   non-zero in-degree are part of, or depend on, a cycle.
+  ///
+  /// * `->` - The indices of the handlers forming a cycle, in order.
+  fn find_cycle(&self, edges: &[Vec<usize>], in_degrees: &[usize]) -> Vec<usize> {
+    // Every unsorted handler has an unsorted predecessor, so walking edges
+    // backwards from any of them eventually loops onto itself.
+    let remaining = |index: usize| in_degrees[index] > 0;
+    let predecessor = |index: usize| {
+      (0..edges.len())
+        .find(|&from| remaining(from) && edges[from].contains(&index))
+        .unwrap()
+    };
+
+    let mut path = Vec::new();
+    let mut current = (0..edges.len()).find(|&i| remaining(i)).unwrap();
+
+    while !path.contains(&current) {
+      path.push(current);
+      current = predecessor(current);
+    }
+
+    let start = path.iter().position(|&i| i == current).unwrap();
+    let mut cycle = path.split_off(start);
+    cycle.reverse();
+
+    // Start from the earliest inserted handler for a stable report.
+    let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(first);
+
+    cycle
   }
 }
 
 /// A trait used for defining labels for [`Schedule`]s.
 ///
 /// This trait is mainly used by the [`ScheduleLabel`] macro to help quickly
-/// create scheduling labels.
-pub trait ScheduleLabel {}
+/// create scheduling labels. Unit structs are identified by their type alone,
+/// while enums and structs with fields are identified by their value and must
+/// implement [`Clone`], [`Eq`] and [`Hash`], so that each variant of an enum
+/// label names a different [`Schedule`]. Their fields must also implement
+/// [`std::fmt::Debug`], as they are part of the name describing the label.
+pub trait ScheduleLabel {
+  /// Returns the key identifying the [`Schedule`] of the label.
+  fn key(&self) -> ScheduleKey;
+}
+
+/// A type-erased label value that can be compared and hashed.
+trait DynLabel: Any + Send + Sync {
+  /// Returns whether the value is equal to another label value.
+  fn dyn_eq(&self, other: &dyn DynLabel) -> bool;
+
+  /// Feeds the value into a hasher.
+  fn dyn_hash(&self, state: &mut dyn Hasher);
+
+  /// Returns the value as [`Any`] to allow downcasting it.
+  fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> DynLabel for T {
+  fn dyn_eq(&self, other: &dyn DynLabel) -> bool {
+    other.as_any().downcast_ref::<T>() == Some(self)
+  }
+
+  fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+    TypeId::of::<T>().hash(&mut state);
+    self.hash(&mut state);
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
+
+/// A key identifying a [`Schedule`] within a [`Scheduler`], built from the
+/// value of its [`ScheduleLabel`].
+///
+/// Keys of labels of different types never compare equal, even when their
+/// values hash the same way. Keys also carry a name describing the label, which
+/// is only used for debugging and does not take part in comparisons.
+#[derive(Clone)]
+pub struct ScheduleKey {
+  value: Arc<dyn DynLabel>,
+  name: Arc<str>,
+}
+
+impl ScheduleKey {
+  /// Creates a new [`ScheduleKey`] named after the type of its value.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The value identifying the [`Schedule`].
+  ///
+  /// * `->` - A new [`ScheduleKey`].
+  pub fn new<T: Eq + Hash + Send + Sync + 'static>(value: T) -> Self {
+    Self {
+      value: Arc::new(value),
+      name: std::any::type_name::<T>().into(),
+    }
+  }
+
+  /// Creates a new [`ScheduleKey`] identifying a [`Schedule`] by a type alone.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - A new [`ScheduleKey`].
+  pub fn of<T: 'static>() -> Self {
+    Self::new(TypeId::of::<T>()).with_name(std::any::type_name::<T>())
+  }
+
+  /// Replaces the name describing the label.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The new name.
+  ///
+  /// * `->` - The renamed [`ScheduleKey`].
+  pub fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+    self.name = name.into();
+
+    self
+  }
+
+  /// Returns the name describing the label.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+impl PartialEq for ScheduleKey {
+  fn eq(&self, other: &Self) -> bool {
+    self.value.dyn_eq(other.value.as_ref())
+  }
+}
+
+impl Eq for ScheduleKey {}
+
+impl Hash for ScheduleKey {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.dyn_hash(state);
+  }
+}
 
 /// A structure used for creating different [`Schedule`]s with [`Handler`]s
 /// effectively allowing for a system to be executed in a real-time manner.
 #[derive(Default)]
 pub struct Scheduler {
-  schedules: HashMap<TypeId, Schedule>,
+  schedules: HashMap<ScheduleKey, Schedule>,
+  running: usize,
+  pending: Vec<(HandlerId, HandlerChange)>,
 }
 
 impl Scheduler {
   /// Executes a [`Schedule`] with a specific [`State`] based on the given
   /// [`ScheduleLabel`].
   ///
-  /// The label parameter exists for consistency with the
-  /// [`Scheduler::add_handler`] method.
-  ///
   /// # Arguments
   ///
   /// * `label` - The label used to find the [`Schedule`] which will be
   ///   executed.
   /// * `state` - The [`State`] to be used by the [`Schedule`]s.
-  #[allow(unused_variables)]
   pub fn run<R: ScheduleLabel + 'static>(&mut self, label: R, state: &mut State) {
-    let key = TypeId::of::<R>();
+    self.run_by_key(label.key(), state);
+  }
+
+  /// Executes a [`Schedule`] with a specific [`State`] based on the key of its
+  /// [`ScheduleLabel`].
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key of the label of the [`Schedule`] to execute.
+  /// * `state` - The [`State`] to be used by the [`Schedule`]s.
+  pub(crate) fn run_by_key(&mut self, key: ScheduleKey, state: &mut State) {
+    // Take the schedule out so that exclusive handlers can use the scheduler.
+    if let Some(mut schedule) = self.schedules.remove(&key) {
+      // Only time the handlers while a profiler is part of the state.
+      let recorder = state.has::<Profiler>().then(Recorder::default);
+      let start = Instant::now();
+
+      self.running += 1;
+      schedule.run(state, self, recorder.as_ref());
+      self.running -= 1;
+
+      if let Some(recorder) = recorder {
+        if let Some(mut profiler) = state.get::<Option<ResMut<Profiler>>>() {
+          profiler.record(key.name(), start, recorder);
+        }
+      }
+
+      // Keep what exclusive handlers added to the schedule while it was running.
+      if let Some(added) = self.schedules.remove(&key) {
+        schedule.merge(added);
+      }
 
-    if let Some(schedule) = self.schedules.get_mut(&key) {
-      schedule.run(state);
+      self.schedules.insert(key, schedule);
+
+      // Apply the changes targeting handlers of schedules that were running.
+      for (id, change) in std::mem::take(&mut self.pending) {
+        self.change_handler(id, change);
+      }
     }
   }
 
@@ -368,21 +2126,161 @@ impl Scheduler {
   /// * `label` - The label used to find the [`Schedule`] onto which the handler
   ///   should be added.
   /// * `handler` - The handler to be added to the [`Schedule`].
-  #[allow(unused_variables)]
-  pub fn add_handler<R: ScheduleLabel + 'static, I, S: Handler + 'static>(
+  ///
+  /// * `->` - The identifier of the added handler.
+  ///
+  /// The handler can be configured with ordering constraints through the
+  /// [`IntoHandlerConfig`] methods before being added.
+  ///
+  /// Panics if the handler requests conflicting accesses to a structure. See
+  /// [`Scheduler::try_add_handler`] for a non-panicking alternative.
+  pub fn add_handler<R: ScheduleLabel + 'static, I>(
     &mut self,
     label: R,
-    handler: impl IntoHandler<I, Handler = S>,
-  ) {
-    let key = TypeId::of::<R>();
+    handler: impl IntoHandlerConfig<I>,
+  ) -> HandlerId {
+    self.add_handler_by_key(label.key(), handler.into_config())
+  }
 
-    if let Some(schedule) = self.schedules.get_mut(&key) {
-      schedule.add_handler(handler);
-    } else {
-      let mut schedule = Schedule::default();
-      schedule.add_handler(handler);
+  /// Adds a [`HandlerConfig`] to a [`Schedule`] based on the key of its
+  /// [`ScheduleLabel`], panicking if the handler requests conflicting accesses
+  /// to a structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key of the label of the [`Schedule`].
+  /// * `handler` - The handler to be added to the [`Schedule`].
+  ///
+  /// * `->` - The identifier of the added handler.
+  pub(crate) fn add_handler_by_key(
+    &mut self,
+    key: ScheduleKey,
+    handler: HandlerConfig,
+  ) -> HandlerId {
+    match self.schedules.entry(key).or_default().add_handler(handler) {
+      Ok(id) => id,
+      Err(error) => panic!("{}", error),
+    }
+  }
 
-      self.schedules.insert(key, schedule);
+  /// Adds a [`Handler`] function to the specified [`Schedule`] using a
+  /// [`ScheduleLabel`], returning an error instead of panicking if the handler
+  /// requests conflicting accesses to a structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The label used to find the [`Schedule`] onto which the handler
+  ///   should be added.
+  /// * `handler` - The handler to be added to the [`Schedule`].
+  ///
+  /// * `->` - The identifier of the added handler, or an error if the
+  ///   handler's accesses conflict with each other.
+  pub fn try_add_handler<R: ScheduleLabel + 'static, I>(
+    &mut self,
+    label: R,
+    handler: impl IntoHandlerConfig<I>,
+  ) -> Result<HandlerId, ScheduleError> {
+    self
+      .schedules
+      .entry(label.key())
+      .or_default()
+      .add_handler(handler)
+  }
+
+  /// Removes a handler from the [`Schedule`] it was added to. Does nothing if
+  /// no handler has the given identifier.
+  ///
+  /// Handlers of a schedule that is currently running are removed once it has
+  /// completed.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler to remove.
+  pub fn remove_handler(&mut self, id: HandlerId) {
+    self.change_handler(id, HandlerChange::Remove);
+  }
+
+  /// Enables or disables a handler. Disabled handlers are skipped when their
+  /// [`Schedule`] runs, while keeping their local data. Does nothing if no
+  /// handler has the given identifier.
+  ///
+  /// Handlers of a schedule that is currently running are changed once it has
+  /// completed.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler.
+  /// * `enabled` - Whether the handler should run.
+  pub fn set_handler_enabled(&mut self, id: HandlerId, enabled: bool) {
+    self.change_handler(id, HandlerChange::Enable(enabled));
+  }
+
+  /// Applies a change to a handler, deferring it while schedules are running
+  /// in case the handler belongs to one of them.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The identifier of the handler.
+  /// * `change` - The change to apply.
+  fn change_handler(&mut self, id: HandlerId, change: HandlerChange) {
+    let found = self
+      .schedules
+      .values_mut()
+      .any(|schedule| schedule.change_handler(id, change));
+
+    if !found && self.running > 0 {
+      self.pending.push((id, change));
     }
   }
+
+  /// Configures the ordering constraints of a named handler set within the
+  /// specified [`Schedule`].
+  ///
+  /// # Arguments
+  ///
+  /// * `label` - The label used to find the [`Schedule`] containing the set.
+  /// * `set` - The [`HandlerSet`] configuration.
+  pub fn configure_set<R: ScheduleLabel + 'static>(&mut self, label: R, set: HandlerSet) {
+    self
+      .schedules
+      .entry(label.key())
+      .or_default()
+      .configure_set(set);
+  }
+
+  /// Resolves the handler order of every [`Schedule`].
+  ///
+  /// Schedules are otherwise built lazily when they first run, which panics if
+  /// their ordering constraints cannot be satisfied. This allows reporting
+  /// these errors ahead of time instead.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The first error encountered while building the schedules.
+  pub fn build(&mut self) -> Result<(), ScheduleError> {
+    self.schedules.values_mut().try_for_each(Schedule::build)
+  }
+
+  /// Describes every [`Schedule`] along with its handlers in the order they
+  /// run and the structures they access, for debugging purposes. See
+  /// [`crate::introspection`] to turn the descriptions into a listing or a
+  /// graph.
+  ///
+  /// Schedules are built first, which is why this can fail.
+  ///
+  /// # Arguments
+  ///
+  /// * `->` - The descriptions of the schedules, sorted by name.
+  pub fn describe(&mut self) -> Result<Vec<ScheduleInfo>, ScheduleError> {
+    self.build()?;
+
+    let mut schedules: Vec<_> = self
+      .schedules
+      .iter()
+      .map(|(key, schedule)| schedule.describe(key.name()))
+      .collect();
+    schedules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(schedules)
+  }
 }
@@ -17,13 +17,18 @@ impl Texture {
   /// * `queue` - The wgpu queue to submit the texture creation command.
   /// * `path` - The path to the image used to create the texture.
   ///
-  /// * `->` A new [`Texture`] created from the image.
-  pub fn new<P>(device: &wgpu::Device, queue: &wgpu::Queue, path: P) -> Self
+  /// * `->` A new [`Texture`] created from the image or an error if the image
+  ///   could not be opened.
+  pub fn new<P>(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    path: P,
+  ) -> image::ImageResult<Self>
   where
     P: AsRef<Path>,
   {
     // Attempt to open the image from path
-    let image = image::open(path).unwrap().to_rgba8();
+    let image = image::open(path)?.to_rgba8();
 
     // Create texture size from dimensions
     let (width, height) = image.dimensions();
@@ -79,7 +84,7 @@ impl Texture {
       },
     );
 
-    Self { view, sampler }
+    Ok(Self { view, sampler })
   }
 
   /// Return a read-only reference to the texture view
@@ -0,0 +1,501 @@
+use crate::{
+  app::{Commands, FixedUpdate},
+  events::{Event, EventBus},
+  state::{Res, ResMut, Scheduler, State},
+};
+
+use std::time::{Duration, Instant};
+
+/// A structure keeping track of time for the application.
+///
+/// It is updated at the beginning of every iteration of the main loop and
+/// holds both the real time, as measured by the clock, and the virtual time,
+/// which can be paused and scaled and is the one handlers should animate with.
+pub struct Time {
+  last_update: Option<Instant>,
+  delta: Duration,
+  elapsed: Duration,
+  real_delta: Duration,
+  real_elapsed: Duration,
+  frame: u64,
+  scale: f64,
+  paused: bool,
+}
+
+impl Default for Time {
+  fn default() -> Self {
+    Self {
+      last_update: None,
+      delta: Duration::ZERO,
+      elapsed: Duration::ZERO,
+      real_delta: Duration::ZERO,
+      real_elapsed: Duration::ZERO,
+      frame: 0,
+      scale: 1.0,
+      paused: false,
+    }
+  }
+}
+
+impl Time {
+  /// Returns the virtual time elapsed since the previous iteration, or the
+  /// [`FixedTime::step`] while the [`FixedUpdate`] schedule runs.
+  pub fn delta(&self) -> Duration {
+    self.delta
+  }
+
+  /// Returns the virtual time elapsed since the previous iteration, in seconds,
+  /// or the [`FixedTime::step`] while the [`FixedUpdate`] schedule runs.
+  pub fn delta_secs(&self) -> f32 {
+    self.delta.as_secs_f32()
+  }
+
+  /// Returns the virtual time elapsed since the application started.
+  pub fn elapsed(&self) -> Duration {
+    self.elapsed
+  }
+
+  /// Returns the virtual time elapsed since the application started, in
+  /// seconds.
+  pub fn elapsed_secs(&self) -> f32 {
+    self.elapsed.as_secs_f32()
+  }
+
+  /// Returns the real time elapsed since the previous iteration, regardless of
+  /// pause and scale.
+  pub fn real_delta(&self) -> Duration {
+    self.real_delta
+  }
+
+  /// Returns the real time elapsed since the application started, regardless
+  /// of pause and scale.
+  pub fn real_elapsed(&self) -> Duration {
+    self.real_elapsed
+  }
+
+  /// Returns the index of the current iteration of the main loop, starting at
+  /// `1` for the first one.
+  pub fn frame(&self) -> u64 {
+    self.frame
+  }
+
+  /// Returns the factor by which virtual time is scaled relative to real time.
+  pub fn scale(&self) -> f64 {
+    self.scale
+  }
+
+  /// Sets the factor by which virtual time is scaled relative to real time.
+  ///
+  /// # Arguments
+  ///
+  /// * `scale` - The new scale factor, `1.0` being real time.
+  pub fn set_scale(&mut self, scale: f64) {
+    assert!(scale >= 0.0, "The time scale must not be negative.");
+
+    self.scale = scale;
+  }
+
+  /// Returns whether virtual time is paused.
+  pub fn is_paused(&self) -> bool {
+    self.paused
+  }
+
+  /// Pauses virtual time. Real time keeps running.
+  pub fn pause(&mut self) {
+    self.paused = true;
+  }
+
+  /// Resumes virtual time.
+  pub fn resume(&mut self) {
+    self.paused = false;
+  }
+
+  /// Starts a new iteration, measuring the real time elapsed since the
+  /// previous one with the clock.
+  pub fn update(&mut self) {
+    let now = Instant::now();
+    let real_delta = self.last_update.map_or(Duration::ZERO, |last| now - last);
+    self.last_update = Some(now);
+
+    self.advance(real_delta);
+  }
+
+  /// Starts a new iteration after a given amount of real time.
+  ///
+  /// # Arguments
+  ///
+  /// * `real_delta` - The real time elapsed since the previous iteration.
+  pub fn advance(&mut self, real_delta: Duration) {
+    self.real_delta = real_delta;
+    self.real_elapsed += real_delta;
+
+    self.delta = match self.paused {
+      true => Duration::ZERO,
+      false => real_delta.mul_f64(self.scale),
+    };
+
+    self.elapsed += self.delta;
+    self.frame += 1;
+  }
+}
+
+/// The modes a [`Timer`] can run in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerMode {
+  /// The timer finishes once and stays finished until it is reset.
+  Once,
+
+  /// The timer starts over whenever it finishes.
+  Repeating,
+}
+
+/// A structure counting down a duration, to be ticked by handlers with the
+/// [`Time`] delta.
+pub struct Timer {
+  duration: Duration,
+  elapsed: Duration,
+  mode: TimerMode,
+  times_finished: u32,
+  finished: bool,
+  paused: bool,
+}
+
+impl Timer {
+  /// Creates a new [`Timer`].
+  ///
+  /// # Arguments
+  ///
+  /// * `duration` - The duration after which the timer finishes.
+  /// * `mode` - Whether the timer finishes once or repeatedly.
+  ///
+  /// * `->` - A new [`Timer`].
+  pub fn new(duration: Duration, mode: TimerMode) -> Self {
+    Self {
+      duration,
+      elapsed: Duration::ZERO,
+      mode,
+      times_finished: 0,
+      finished: false,
+      paused: false,
+    }
+  }
+
+  /// Advances the timer.
+  ///
+  /// # Arguments
+  ///
+  /// * `delta` - The time elapsed since the last tick.
+  ///
+  /// * `->` - A mutable reference to the [`Timer`].
+  pub fn tick(&mut self, delta: Duration) -> &mut Self {
+    self.times_finished = 0;
+
+    // A zero duration has elapsed from the start, so timers running once only
+    // stop once they reported finishing.
+    if self.paused || self.finished {
+      return self;
+    }
+
+    self.elapsed += delta;
+
+    if self.elapsed < self.duration {
+      return self;
+    }
+
+    match self.mode {
+      TimerMode::Once => {
+        self.elapsed = self.duration;
+        self.times_finished = 1;
+        self.finished = true;
+      }
+      TimerMode::Repeating if self.duration.is_zero() => {
+        self.elapsed = Duration::ZERO;
+        self.times_finished = 1;
+      }
+      TimerMode::Repeating => {
+        let times = self.elapsed.as_nanos() / self.duration.as_nanos();
+        self.elapsed -= self.duration * times as u32;
+        self.times_finished = times as u32;
+      }
+    }
+
+    self
+  }
+
+  /// Advances the timer and writes an event onto an [`EventBus`] every time
+  /// it finishes.
+  ///
+  /// # Arguments
+  ///
+  /// * `delta` - The time elapsed since the last tick.
+  /// * `events` - The [`EventBus`] to write events to.
+  /// * `event` - A function creating the event to write.
+  ///
+  /// * `->` - A mutable reference to the [`Timer`].
+  pub fn tick_and_notify<E: Event + 'static>(
+    &mut self,
+    delta: Duration,
+    events: &mut EventBus,
+    mut event: impl FnMut() -> E,
+  ) -> &mut Self {
+    for _ in 0..self.tick(delta).times_finished {
+      events.write(event());
+    }
+
+    self
+  }
+
+  /// Returns whether a timer running once has finished.
+  pub fn is_finished(&self) -> bool {
+    self.finished
+  }
+
+  /// Returns whether the timer finished during the last tick.
+  pub fn just_finished(&self) -> bool {
+    self.times_finished > 0
+  }
+
+  /// Returns how many times the timer finished during the last tick, which can
+  /// be more than once for repeating timers ticked by large deltas.
+  pub fn times_finished(&self) -> u32 {
+    self.times_finished
+  }
+
+  /// Returns the time elapsed since the timer started or last finished.
+  pub fn elapsed(&self) -> Duration {
+    self.elapsed
+  }
+
+  /// Returns the time left before the timer finishes.
+  pub fn remaining(&self) -> Duration {
+    self.duration.saturating_sub(self.elapsed)
+  }
+
+  /// Returns the duration after which the timer finishes.
+  pub fn duration(&self) -> Duration {
+    self.duration
+  }
+
+  /// Pauses the timer, which ignores ticks until it is resumed.
+  pub fn pause(&mut self) {
+    self.paused = true;
+  }
+
+  /// Resumes the timer.
+  pub fn resume(&mut self) {
+    self.paused = false;
+  }
+
+  /// Restarts the timer from zero.
+  pub fn reset(&mut self) {
+    self.elapsed = Duration::ZERO;
+    self.times_finished = 0;
+    self.finished = false;
+  }
+}
+
+/// A structure measuring the time elapsed while it runs, to be ticked by
+/// handlers with the [`Time`] delta.
+#[derive(Default)]
+pub struct Stopwatch {
+  elapsed: Duration,
+  paused: bool,
+}
+
+impl Stopwatch {
+  /// Advances the stopwatch unless it is paused.
+  ///
+  /// # Arguments
+  ///
+  /// * `delta` - The time elapsed since the last tick.
+  ///
+  /// * `->` - A mutable reference to the [`Stopwatch`].
+  pub fn tick(&mut self, delta: Duration) -> &mut Self {
+    if !self.paused {
+      self.elapsed += delta;
+    }
+
+    self
+  }
+
+  /// Returns the time measured by the stopwatch.
+  pub fn elapsed(&self) -> Duration {
+    self.elapsed
+  }
+
+  /// Returns the time measured by the stopwatch, in seconds.
+  pub fn elapsed_secs(&self) -> f32 {
+    self.elapsed.as_secs_f32()
+  }
+
+  /// Returns whether the stopwatch is paused.
+  pub fn is_paused(&self) -> bool {
+    self.paused
+  }
+
+  /// Pauses the stopwatch, which ignores ticks until it is resumed.
+  pub fn pause(&mut self) {
+    self.paused = true;
+  }
+
+  /// Resumes the stopwatch.
+  pub fn resume(&mut self) {
+    self.paused = false;
+  }
+
+  /// Resets the measured time to zero.
+  pub fn reset(&mut self) {
+    self.elapsed = Duration::ZERO;
+  }
+}
+
+/// A structure configuring the rate at which the [`FixedUpdate`] schedule runs
+/// and keeping track of the time it still has to catch up on.
+///
+/// The time elapsed between two iterations of the main loop is accumulated and
+/// consumed in steps of a fixed duration, each step running the
+/// [`FixedUpdate`] schedule once. The time left over is exposed as an
+/// interpolation factor between the last two steps.
+pub struct FixedTime {
+  step: Duration,
+  max_steps: u32,
+  accumulator: Duration,
+}
+
+/// Runs the fixed update schedule at 60 Hz by default.
+impl Default for FixedTime {
+  fn default() -> Self {
+    Self::from_hz(60.0)
+  }
+}
+
+impl FixedTime {
+  /// Creates a new [`FixedTime`] with a specific step duration.
+  ///
+  /// # Arguments
+  ///
+  /// * `step` - The duration of a step.
+  ///
+  /// * `->` - A new [`FixedTime`] catching up on at most 5 steps per iteration.
+  pub fn new(step: Duration) -> Self {
+    assert!(!step.is_zero(), "The fixed time step must not be zero.");
+
+    Self {
+      step,
+      max_steps: 5,
+      accumulator: Duration::ZERO,
+    }
+  }
+
+  /// Creates a new [`FixedTime`] running a specific number of steps per second.
+  ///
+  /// # Arguments
+  ///
+  /// * `hz` - The number of steps per second.
+  ///
+  /// * `->` - A new [`FixedTime`].
+  pub fn from_hz(hz: f64) -> Self {
+    Self::new(Duration::from_secs_f64(1.0 / hz))
+  }
+
+  /// Sets the maximum number of steps run in a single iteration of the main
+  /// loop. Steps beyond that are dropped, which slows the simulation down
+  /// rather than stalling the application when it cannot keep up.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_steps` - The maximum number of steps per iteration.
+  ///
+  /// * `->` - The updated [`FixedTime`].
+  pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+    self.max_steps = max_steps;
+
+    self
+  }
+
+  /// Returns the duration of a step.
+  pub fn step(&self) -> Duration {
+    self.step
+  }
+
+  /// Returns the maximum number of steps run in a single iteration of the main
+  /// loop.
+  pub fn max_steps(&self) -> u32 {
+    self.max_steps
+  }
+
+  /// Returns the time accumulated towards the next step.
+  pub fn accumulator(&self) -> Duration {
+    self.accumulator
+  }
+
+  /// Returns how far the accumulated time is into the next step, between `0.0`
+  /// and `1.0`, which can be used to interpolate between the states of the last
+  /// two steps when rendering.
+  pub fn alpha(&self) -> f32 {
+    self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+  }
+
+  /// Accumulates elapsed time and consumes it in steps.
+  ///
+  /// # Arguments
+  ///
+  /// * `delta` - The time elapsed since the last call.
+  ///
+  /// * `->` - The number of steps to run, at most [`FixedTime::max_steps`].
+  pub fn advance(&mut self, delta: Duration) -> u32 {
+    self.accumulator += delta;
+
+    let steps = (self.accumulator.as_nanos() / self.step.as_nanos()) as u32;
+    self.accumulator -= self.step * steps;
+
+    steps.min(self.max_steps)
+  }
+}
+
+/// Updates the [`Time`] of the application at the start of an iteration.
+///
+/// # Arguments
+///
+/// * `time` - The [`Time`] to update.
+pub(crate) fn update_time(mut time: ResMut<Time>) {
+  time.update();
+}
+
+/// Runs the [`FixedUpdate`] schedule as many times as the [`FixedTime`] of the
+/// state requires for the virtual time elapsed since the previous iteration,
+/// applying the commands queued after each run. The [`Time`] delta is the
+/// fixed step while the schedule runs.
+///
+/// # Arguments
+///
+/// * `state` - The application [`State`].
+/// * `scheduler` - The application [`Scheduler`].
+pub(crate) fn run_fixed_update(state: &mut State, scheduler: &mut Scheduler) {
+  let delta = state
+    .get::<Option<Res<Time>>>()
+    .map_or(Duration::ZERO, |time| time.delta());
+
+  let Some((steps, step)) = state
+    .get::<Option<ResMut<FixedTime>>>()
+    .map(|mut time| (time.advance(delta), time.step()))
+  else {
+    return;
+  };
+
+  // Handlers of the fixed update schedule see the fixed step as their delta.
+  let set_delta = |state: &mut State, delta: Duration| {
+    if let Some(mut time) = state.get::<Option<ResMut<Time>>>() {
+      time.bypass_change_detection().delta = delta;
+    }
+  };
+
+  set_delta(state, step);
+
+  for _ in 0..steps {
+    scheduler.run(FixedUpdate, state);
+    Commands::apply_queued(state, scheduler);
+  }
+
+  set_delta(state, delta);
+}
@@ -1,3 +1,8 @@
+use crate::{
+  ecs::{Component, Entity, Query, With, Without},
+  hierarchy::{Children, Parent},
+};
+
 use encase::ShaderType;
 
 use glam::{Affine2, Vec2};
@@ -21,8 +26,21 @@ impl From<Transform> for AffineTransform {
   ///
   /// * `->` A new affine transformation created from the given transform.
   fn from(value: Transform) -> Self {
-    let affine =
-      Affine2::from_scale_angle_translation(value.scale, value.rotation, value.position);
+    AffineTransform::from(GlobalTransform::from(value))
+  }
+}
+
+impl From<GlobalTransform> for AffineTransform {
+  /// Creates a new affine transformation from a global transform.
+  ///
+  /// # Arguments
+  ///
+  /// * `transform` - Global transform from which to create a new affine
+  ///   transformation
+  ///
+  /// * `->` A new affine transformation created from the given transform.
+  fn from(value: GlobalTransform) -> Self {
+    let affine = value.affine();
 
     AffineTransform {
       matrix2: affine.matrix2,
@@ -34,8 +52,11 @@ impl From<Transform> for AffineTransform {
 /// A structure that represents a transform which describes the position,
 /// rotation and scale of an object. Usually used in structures that have world
 /// positions such as a [`crate::mesh::Mesh`] or a [`crate::camera::Camera`].
+///
+/// As a component, it is relative to the [`Parent`] of its entity, if any. The
+/// resulting transform relative to the world is its [`GlobalTransform`].
 /// TODO: Implement look_at method.
-#[derive(Debug, Copy, Clone)]
+#[derive(Component, Debug, Copy, Clone)]
 pub struct Transform {
   /// The position of the transform.
   pub position: Vec2,
@@ -57,3 +78,128 @@ impl Default for Transform {
     }
   }
 }
+
+impl Transform {
+  /// Creates a new transform from an affine transformation. Shearing, which
+  /// results from composing a non-uniform scale with a rotation, is lost.
+  ///
+  /// # Arguments
+  ///
+  /// * `affine` - The affine transformation to decompose.
+  ///
+  /// * `->` - A new [`Transform`].
+  pub fn from_affine(affine: Affine2) -> Self {
+    let (scale, rotation, position) = affine.to_scale_angle_translation();
+
+    Self {
+      position,
+      rotation,
+      scale,
+    }
+  }
+
+  /// Returns the affine transformation of the transform.
+  pub fn affine(&self) -> Affine2 {
+    Affine2::from_scale_angle_translation(self.scale, self.rotation, self.position)
+  }
+}
+
+/// A component holding the transform of an entity relative to the world.
+///
+/// It is computed from the [`Transform`] of the entity and those of its
+/// ancestors during [`crate::app::PostUpdate`], in the `"transforms"` handler
+/// set, so it lags behind changes made later in the frame. Entities need both
+/// components for it to be propagated.
+#[derive(Component, Debug, Copy, Clone, PartialEq)]
+pub struct GlobalTransform(Affine2);
+
+impl Default for GlobalTransform {
+  fn default() -> Self {
+    Self(Affine2::IDENTITY)
+  }
+}
+
+impl From<Transform> for GlobalTransform {
+  fn from(value: Transform) -> Self {
+    Self(value.affine())
+  }
+}
+
+impl GlobalTransform {
+  /// Returns the affine transformation from the entity to the world.
+  pub fn affine(&self) -> Affine2 {
+    self.0
+  }
+
+  /// Returns the position of the entity in the world.
+  pub fn translation(&self) -> Vec2 {
+    self.0.translation
+  }
+
+  /// Returns the transform relative to the world.
+  pub fn to_transform(&self) -> Transform {
+    Transform::from_affine(self.0)
+  }
+}
+
+/// Computes the [`GlobalTransform`] of every entity from its [`Transform`] and
+/// those of its ancestors, starting from the entities without a [`Parent`].
+/// Entities without a [`Transform`] do not move their children, as in
+/// [`State::world_affine`](crate::state::State::world_affine). Global
+/// transforms are only written when they change.
+///
+/// # Arguments
+///
+/// * `roots` - The entities without a parent that have a transform.
+/// * `parents` - The entities without a parent that have children.
+/// * `transforms` - The transforms of the entities.
+/// * `children` - The children of the entities.
+/// * `globals` - The global transforms to update.
+pub fn propagate_transforms(
+  roots: Query<Entity, (With<Transform>, Without<Parent>)>,
+  parents: Query<Entity, (With<Children>, Without<Parent>)>,
+  transforms: Query<&Transform>,
+  children: Query<&Children>,
+  mut globals: Query<&mut GlobalTransform>,
+) {
+  let bare_roots = parents
+    .iter()
+    .filter(|&entity| !transforms.contains(entity));
+
+  for entity in roots.iter().chain(bare_roots) {
+    propagate(
+      entity,
+      Affine2::IDENTITY,
+      &transforms,
+      &children,
+      &mut globals,
+    );
+  }
+}
+
+/// Sets the [`GlobalTransform`] of an entity from the one of its parent and
+/// propagates it to its descendants.
+fn propagate(
+  entity: Entity,
+  parent: Affine2,
+  transforms: &Query<&Transform>,
+  children: &Query<&Children>,
+  globals: &mut Query<&mut GlobalTransform>,
+) {
+  let affine = parent
+    * transforms
+      .get(entity)
+      .map_or(Affine2::IDENTITY, Transform::affine);
+
+  // Only borrow the global transform mutably when it moved, so that the
+  // storage is not marked as changed every frame.
+  if globals.get(entity).is_some_and(|global| global.0 != affine) {
+    if let Some(global) = globals.get_mut(entity) {
+      *global = GlobalTransform(affine);
+    }
+  }
+
+  for &child in children.get(entity).into_iter().flat_map(Children::iter) {
+    propagate(child, affine, transforms, children, globals);
+  }
+}
@@ -1,5 +1,5 @@
 use crate::{
-  app::{App, Init, Module, PreInit, Update},
+  app::{App, Init, Module, PostUpdate, PreInit},
   events::{Event, EventBus},
   state::{Res, ResMut, ScheduleLabel},
 };
@@ -13,15 +13,18 @@ use winit::{
   window::{Window as WinitWindow, WindowAttributes},
 };
 
-/// A private schedule label that represents when the window is being redrawn.
+/// A schedule label that represents the preparation of a new frame, before
+/// anything is rendered.
 #[derive(ScheduleLabel)]
-pub(crate) struct Render;
+pub struct BeginRender;
 
+/// A schedule label that represents when the window is being redrawn.
 #[derive(ScheduleLabel)]
-pub(crate) struct BeginRender;
+pub struct Render;
 
+/// A schedule label that represents the presentation of the rendered frame.
 #[derive(ScheduleLabel)]
-pub(crate) struct EndRender;
+pub struct EndRender;
 
 /// An event that represents when the window is resized.
 #[derive(Event)]
@@ -50,7 +53,12 @@ impl WindowModule {
 
 impl Module for WindowModule {
   fn configure(&self, app: &mut App) {
-    app.set_runner(Self::runner);
+    // Render every frame right after the application is updated.
+    app
+      .set_runner(Self::runner)
+      .add_schedule_after(PostUpdate, BeginRender)
+      .add_schedule_after(BeginRender, Render)
+      .add_schedule_after(Render, EndRender);
   }
 }
 
@@ -117,8 +125,8 @@ impl ApplicationHandler<()> for WindowApp {
   }
 
   fn resumed(&mut self, event_loop: &event_loop::ActiveEventLoop) {
-    // Create the application window as a resource
-    self.app.add_state(Window::new(
+    // Create the application window as a main thread resource
+    self.app.add_non_send_state(Window::new(
       event_loop
         .create_window(WindowAttributes::default())
         .unwrap(),
@@ -153,14 +161,8 @@ impl ApplicationHandler<()> for WindowApp {
       }
 
       WindowEvent::RedrawRequested => {
-        // Run the application update schedule
-        self.app.run_schedule(Update);
-
-        // Run the application redraw schedule
-        // TODO: This should be done in a sub system
-        self.app.run_schedule(BeginRender);
-        self.app.run_schedule(Render);
-        self.app.run_schedule(EndRender);
+        // Run the application main schedules, rendering included
+        self.app.run_main_schedules();
 
         // Run the post-loop logic
         self.app.run_post_loop();
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+  use charbs::prelude::*;
+  use std::sync::Mutex;
+
+  #[derive(ScheduleLabel)]
+  struct Physics;
+
+  #[derive(ScheduleLabel)]
+  struct Network;
+
+  static ORDER: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+  fn run_once(app: App) {
+    app.run_main_schedules();
+  }
+
+  #[test]
+  fn schedules_can_be_inserted_around_main_schedules() {
+    App::default()
+      .add_schedule_after(Update, Physics)
+      .add_schedule_before(Physics, Network);
+  }
+
+  #[test]
+  fn inserted_schedules_run_in_the_main_loop() {
+    App::default()
+      .add_schedule_after(Update, Physics)
+      .add_handler(PostUpdate, || ORDER.lock().unwrap().push("post update"))
+      .add_handler(Physics, || ORDER.lock().unwrap().push("physics"))
+      .add_handler(Update, || ORDER.lock().unwrap().push("update"))
+      .set_runner(run_once)
+      .run();
+
+    assert_eq!(
+      *ORDER.lock().unwrap(),
+      vec!["update", "physics", "post update"]
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "app::tests::Physics is not a main schedule.")]
+  fn schedules_cannot_be_inserted_around_unknown_schedules() {
+    App::default().add_schedule_after(Physics, Network);
+  }
+
+  #[test]
+  #[should_panic(expected = "app::tests::Physics is already a main schedule.")]
+  fn schedules_cannot_be_inserted_twice() {
+    App::default()
+      .add_schedule_after(Update, Physics)
+      .add_schedule_before(Last, Physics);
+  }
+}
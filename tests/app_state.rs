@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{app_state, prelude::*};
+
+  #[derive(ScheduleLabel)]
+  struct Frame;
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+  enum Screen {
+    Menu,
+    Game,
+  }
+
+  fn log(entry: &'static str) -> impl FnMut(ResMut<Vec<&'static str>>) {
+    move |mut log: ResMut<Vec<&'static str>>| log.push(entry)
+  }
+
+  fn setup() -> (State, Scheduler) {
+    let mut state = State::default();
+    state.add(AppState::new(Screen::Menu));
+    state.add(NextState::<Screen>::default());
+    state.add(Vec::<&'static str>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Frame, app_state::apply_transitions::<Screen>());
+    scheduler.add_handler(Frame, app_state::run_on_update::<Screen>);
+
+    scheduler.add_handler(OnEnter(Screen::Menu), log("enter menu"));
+    scheduler.add_handler(OnExit(Screen::Menu), log("exit menu"));
+    scheduler.add_handler(OnEnter(Screen::Game), log("enter game"));
+    scheduler.add_handler(OnUpdate(Screen::Menu), log("update menu"));
+    scheduler.add_handler(OnUpdate(Screen::Game), log("update game"));
+
+    (state, scheduler)
+  }
+
+  #[test]
+  fn transitions_run_exit_and_enter_schedules() {
+    let (mut state, mut scheduler) = setup();
+
+    scheduler.run(Frame, &mut state);
+    state.get::<ResMut<NextState<Screen>>>().set(Screen::Game);
+    scheduler.run(Frame, &mut state);
+
+    assert_eq!(*state.get::<Res<AppState<Screen>>>().get(), Screen::Game);
+    assert_eq!(
+      *state.get::<Res<Vec<&'static str>>>(),
+      vec![
+        "enter menu",
+        "update menu",
+        "exit menu",
+        "enter game",
+        "update game"
+      ]
+    );
+  }
+
+  #[test]
+  fn transitions_to_the_current_state_are_ignored() {
+    let (mut state, mut scheduler) = setup();
+
+    scheduler.run(Frame, &mut state);
+    state.get::<ResMut<NextState<Screen>>>().set(Screen::Menu);
+    scheduler.run(Frame, &mut state);
+
+    assert!(state.get::<Res<NextState<Screen>>>().pending().is_none());
+    assert_eq!(
+      *state.get::<Res<Vec<&'static str>>>(),
+      vec!["enter menu", "update menu", "update menu"]
+    );
+  }
+}
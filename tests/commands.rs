@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use charbs::prelude::*;
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  struct Level(u32);
+
+  fn load_level(mut commands: ResMut<Commands>) {
+    commands
+      .remove_state::<Level>()
+      .add_state(Level(1))
+      .insert_or_update(|| 0_u32, |loads| *loads += 1);
+  }
+
+  fn run(scheduler: &mut Scheduler, state: &mut State) {
+    scheduler.run(Update, state);
+
+    let commands = std::mem::take(&mut *state.get::<ResMut<Commands>>());
+    commands.apply(state);
+  }
+
+  #[test]
+  fn commands_are_applied_in_order() {
+    let mut state = State::default();
+    state.add(Commands::default());
+    state.add(Level(0));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, load_level);
+
+    run(&mut scheduler, &mut state);
+    assert_eq!(state.get::<Res<Level>>().0, 1);
+    assert_eq!(*state.get::<Res<u32>>(), 0);
+
+    run(&mut scheduler, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  #[test]
+  fn removed_structures_are_dropped() {
+    let mut state = State::default();
+    state.add(Level(0));
+
+    let mut commands = Commands::default();
+    commands.remove_state::<Level>().remove_state::<String>();
+    commands.apply(&mut state);
+
+    assert!(!state.has::<Level>());
+  }
+
+  struct Spawn(u32);
+
+  impl Command for Spawn {
+    fn apply(self, state: &mut State) {
+      state.get::<ResMut<Vec<u32>>>().push(self.0);
+    }
+  }
+
+  #[test]
+  fn custom_commands_are_queued_with_closures() {
+    let mut state = State::default();
+    state.add(Vec::<u32>::new());
+
+    let mut commands = Commands::default();
+    commands
+      .queue(Spawn(1))
+      .queue(|state: &mut State| state.get::<ResMut<Vec<u32>>>().clear())
+      .queue(Spawn(2));
+    commands.apply(&mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![2]);
+  }
+
+  fn count(mut runs: ResMut<u32>) {
+    *runs += 1;
+  }
+
+  #[test]
+  fn handlers_are_added_toggled_and_removed() {
+    let mut state = State::default();
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    let mut commands = Commands::default();
+
+    let id = commands.add_handler(Update, count);
+    commands.apply_all(&mut state, &mut scheduler);
+    scheduler.run(Update, &mut state);
+
+    commands
+      .disable_handler(id)
+      .apply_all(&mut state, &mut scheduler);
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+
+    commands
+      .enable_handler(id)
+      .apply_all(&mut state, &mut scheduler);
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 2);
+
+    commands
+      .remove_handler(id)
+      .apply_all(&mut state, &mut scheduler);
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 2);
+  }
+
+  #[test]
+  #[should_panic(
+    expected = "Operations on handlers cannot be applied without a scheduler"
+  )]
+  fn handler_operations_are_not_discarded_without_a_scheduler() {
+    let mut state = State::default();
+    let mut commands = Commands::default();
+
+    commands.add_handler(Update, count);
+    commands.apply(&mut state);
+  }
+
+  struct Log(Arc<Mutex<Vec<&'static str>>>);
+
+  impl Drop for Log {
+    fn drop(&mut self) {
+      self.0.lock().unwrap().push("handler removed");
+    }
+  }
+
+  #[test]
+  fn handler_operations_are_applied_in_order_with_commands() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut state = State::default();
+    let mut scheduler = Scheduler::default();
+    let mut commands = Commands::default();
+
+    let dropped = Log(log.clone());
+    let id = commands.add_handler(Update, move || {
+      let _ = &dropped;
+    });
+    commands.apply_all(&mut state, &mut scheduler);
+
+    let (before, after) = (log.clone(), log.clone());
+    commands
+      .queue(move |_: &mut State| before.lock().unwrap().push("before"))
+      .remove_handler(id)
+      .queue(move |_: &mut State| after.lock().unwrap().push("after"));
+    commands.apply_all(&mut state, &mut scheduler);
+
+    assert_eq!(
+      *log.lock().unwrap(),
+      vec!["before", "handler removed", "after"]
+    );
+  }
+}
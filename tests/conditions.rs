@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+  use charbs::prelude::*;
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  #[derive(Clone, PartialEq)]
+  struct Paused(bool);
+
+  fn count(mut runs: ResMut<u32>) {
+    *runs += 1;
+  }
+
+  fn is_paused(paused: Res<Paused>) -> bool {
+    paused.0
+  }
+
+  #[test]
+  fn handlers_only_run_when_conditions_are_met() {
+    let mut state = State::default();
+    state.add(0_u32);
+    state.add(Paused(true));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, count.run_if(is_paused.not()));
+
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 0);
+
+    state.get::<ResMut<Paused>>().0 = false;
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  #[test]
+  fn conditions_can_be_combined() {
+    let mut state = State::default();
+    state.add(0_u32);
+    state.add(Paused(false));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(
+      Update,
+      count.run_if(resource_exists::<String>().or(resource_equals(Paused(false)))),
+    );
+    scheduler.add_handler(
+      Update,
+      count.run_if(resource_exists::<Paused>().and(resource_exists::<String>())),
+    );
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  #[test]
+  fn resource_changed_is_met_once_per_change() {
+    let mut state = State::default();
+    state.add(0_u32);
+    state.add(Paused(false));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, count.run_if(resource_changed::<Paused>()));
+
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+
+    state.get::<ResMut<Paused>>().0 = true;
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 2);
+  }
+}
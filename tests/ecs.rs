@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+  use charbs::prelude::*;
+
+  #[derive(Component, Debug, PartialEq)]
+  struct Health(u32);
+
+  #[derive(Component, Debug, PartialEq)]
+  struct Velocity(f32);
+
+  #[derive(Component)]
+  struct Frozen;
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  #[test]
+  fn components_are_inserted_and_removed() {
+    let mut state = State::default();
+    let entity = state.spawn((Health(10), Velocity(1.0)));
+
+    state.insert(entity, Health(5));
+    state.remove_components::<Velocity>(entity);
+
+    let health = state.get::<Res<Components<Health>>>();
+    let velocities = state.get::<Res<Components<Velocity>>>();
+
+    assert_eq!(health.get(entity), Some(&Health(5)));
+    assert!(!velocities.contains(entity));
+  }
+
+  #[test]
+  fn despawned_entities_are_not_confused_with_new_ones() {
+    let mut state = State::default();
+    let first = state.spawn(Health(1));
+    let second = state.spawn(Health(2));
+
+    assert!(state.despawn(first));
+    assert!(!state.despawn(first));
+
+    let third = state.spawn(Health(3));
+
+    assert_eq!(third.index(), first.index());
+    assert!(!state.contains(first));
+    assert!(!state.insert(first, Velocity(0.0)));
+
+    let health = state.get::<Res<Components<Health>>>();
+
+    assert_eq!(health.get(first), None);
+    assert_eq!(health.get(second), Some(&Health(2)));
+    assert_eq!(health.get(third), Some(&Health(3)));
+    assert_eq!(state.get::<Res<Entities>>().len(), 2);
+  }
+
+  #[test]
+  fn commands_spawn_and_despawn_entities() {
+    let mut state = State::default();
+    state.add(Entities::default());
+
+    let reserved = state.get::<Res<Entities>>().reserve();
+
+    let mut commands = Commands::default();
+    let spawned = commands.spawn_entity(&state.get::<Res<Entities>>(), Health(1));
+    commands
+      .insert(reserved, (Health(2), Velocity(2.0)))
+      .despawn(reserved)
+      .insert(spawned, Velocity(1.0));
+    commands.apply(&mut state);
+
+    let health = state.get::<Res<Components<Health>>>();
+
+    assert_eq!(health.len(), 1);
+    assert!(!health.contains(reserved));
+    assert_eq!(health.get(spawned), Some(&Health(1)));
+    assert!(state.get::<Res<Components<Velocity>>>().contains(spawned));
+  }
+
+  #[test]
+  fn queries_iterate_matching_entities() {
+    let mut state = State::default();
+    let moving = state.spawn((Health(1), Velocity(2.0)));
+    let frozen = state.spawn((Health(2), Velocity(3.0), Frozen));
+    let idle = state.spawn(Health(3));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(
+      Update,
+      |mut query: Query<(&Velocity, &mut Health), Without<Frozen>>| {
+        for (velocity, health) in query.iter_mut() {
+          health.0 += velocity.0 as u32;
+        }
+      },
+    );
+
+    scheduler.run(Update, &mut state);
+
+    let query = state.get::<Query<(Entity, &Health)>>();
+
+    assert_eq!(query.get(moving), Some((moving, &Health(3))));
+    assert_eq!(query.get(frozen), Some((frozen, &Health(2))));
+    assert_eq!(query.iter().count(), 3);
+    assert!(!state.get::<Query<&Health, With<Velocity>>>().contains(idle));
+  }
+
+  #[test]
+  fn entity_queries_iterate_filtered_entities() {
+    let mut state = State::default();
+    let frozen = state.spawn((Health(1), Frozen));
+    state.spawn(Health(2));
+
+    let query = state.get::<Query<Entity, With<Frozen>>>();
+
+    assert_eq!(query.iter().collect::<Vec<_>>(), vec![frozen]);
+    assert_eq!(
+      state
+        .get::<Query<(Entity, &Health), (With<Frozen>, With<Health>)>>()
+        .iter()
+        .count(),
+      1
+    );
+  }
+
+  #[test]
+  fn queries_on_disjoint_components_run_concurrently() {
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, |_: Query<&mut Health>| {});
+    scheduler.add_handler(Update, |_: Query<&mut Velocity, Without<Frozen>>| {});
+
+    let schedules = scheduler.describe().unwrap();
+    let handlers = schedules[0].handlers();
+
+    assert_eq!(handlers[0].batch(), handlers[1].batch());
+    assert!(
+      scheduler
+        .try_add_handler(Update, |_: Query<(&Health, &mut Health)>| {})
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn filters_on_fetched_components_do_not_conflict() {
+    let mut state = State::default();
+    let entity = state.spawn(Health(1));
+
+    let mut scheduler = Scheduler::default();
+    let added =
+      scheduler.try_add_handler(Update, |mut query: Query<&mut Health, With<Health>>| {
+        for health in query.iter_mut() {
+          health.0 += 1;
+        }
+      });
+
+    assert!(added.is_ok());
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(state.get::<Query<&Health>>().get(entity), Some(&Health(2)));
+    assert_eq!(
+      state
+        .get::<Query<&Health, Without<Health>>>()
+        .iter()
+        .count(),
+      0
+    );
+  }
+
+  #[test]
+  fn queries_split_by_filters_are_accepted() {
+    let mut state = State::default();
+    let frozen = state.spawn((Velocity(1.0), Frozen));
+    let moving = state.spawn(Velocity(2.0));
+
+    let mut scheduler = Scheduler::default();
+    let stop = scheduler.try_add_handler(
+      Update,
+      |mut query: Query<&mut Velocity, With<Frozen>>| {
+        for velocity in query.iter_mut() {
+          velocity.0 = 0.0;
+        }
+      },
+    );
+    let read =
+      scheduler.try_add_handler(Update, |query: Query<&Velocity, Without<Frozen>>| {
+        assert_eq!(query.iter().count(), 1);
+      });
+
+    assert!(stop.is_ok() && read.is_ok());
+    scheduler.run(Update, &mut state);
+
+    let query = state.get::<Query<&Velocity>>();
+
+    assert_eq!(query.get(frozen), Some(&Velocity(0.0)));
+    assert_eq!(query.get(moving), Some(&Velocity(2.0)));
+  }
+}
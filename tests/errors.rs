@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{events::EventBus, prelude::*};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  fn fail() -> Result<(), String> {
+    Err("Something went wrong.".to_string())
+  }
+
+  fn count(mut runs: ResMut<u32>) -> Result<(), BoxedError> {
+    *runs += 1;
+
+    Ok(())
+  }
+
+  #[test]
+  fn handler_errors_are_written_as_events() {
+    let mut state = State::default();
+    state.add(ErrorPolicy::Event);
+    state.add(EventBus::default());
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, fail);
+    scheduler.add_handler(Update, count);
+
+    scheduler.run(Update, &mut state);
+
+    let errors = state.get::<ResMut<EventBus>>().read::<HandlerError>();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].handler(), "errors::tests::fail");
+    assert_eq!(errors[0].error().to_string(), "Something went wrong.");
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  static FAILURES: AtomicUsize = AtomicUsize::new(0);
+
+  #[test]
+  fn handler_errors_are_passed_to_custom_policies() {
+    let mut state = State::default();
+    state.add(ErrorPolicy::Custom(|_| {
+      FAILURES.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, fail);
+    scheduler.add_handler(Update, fail);
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(FAILURES.load(Ordering::SeqCst), 2);
+  }
+
+  #[test]
+  #[should_panic(expected = "Handler errors::tests::fail failed: Something went wrong.")]
+  fn handler_errors_panic_by_default() {
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, fail);
+
+    scheduler.run(Update, &mut State::default());
+  }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+  use charbs::prelude::*;
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  struct Speed(u32);
+
+  struct Position(u32);
+
+  #[derive(HandlerParam)]
+  struct Movement<'a> {
+    speed: Res<'a, Speed>,
+    position: ResMut<'a, Position>,
+    steps: Local<'a, u32>,
+  }
+
+  #[derive(HandlerParam)]
+  struct Optional<'a, T: Send + Sync + 'static>(Option<Res<'a, T>>);
+
+  fn step(mut movement: Movement) {
+    *movement.steps += 1;
+    movement.position.0 += movement.speed.0 * *movement.steps;
+  }
+
+  #[test]
+  fn derived_params_are_injected() {
+    let mut state = State::default();
+    state.add(Speed(2));
+    state.add(Position(0));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, step);
+
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(state.get::<Res<Position>>().0, 6);
+  }
+
+  #[test]
+  fn derived_params_declare_their_accesses() {
+    let mut scheduler = Scheduler::default();
+
+    let result = scheduler.try_add_handler(Update, |_: Movement, _: Res<Position>| {});
+
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn generic_derived_params_are_injected() {
+    let mut state = State::default();
+    state.add(Speed(2));
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(
+      Update,
+      |speed: Optional<Speed>, position: Optional<Position>, mut found: ResMut<u32>| {
+        *found = speed.0.is_some() as u32 + position.0.is_some() as u32;
+      },
+    );
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  #[test]
+  fn nested_tuples_exceed_the_parameter_limit() {
+    let mut state = State::default();
+    state.add(1_u8);
+    state.add(2_u16);
+    state.add(3_u32);
+    state.add(4_u64);
+    state.add(0_i64);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(
+      Update,
+      |a: (Res<u8>, Res<u16>, Res<u32>, Res<u64>),
+       b: (Res<u8>, Res<u16>, Res<u32>, Res<u64>),
+       c: (Res<u8>, Res<u16>, Res<u32>, Res<u64>),
+       mut total: ResMut<i64>| {
+        for (a, b, c, d) in [a, b, c] {
+          *total += *a as i64 + *b as i64 + *c as i64 + *d as i64;
+        }
+      },
+    );
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<i64>>(), 30);
+  }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{prelude::*, transform};
+  use glam::Vec2;
+
+  #[derive(ScheduleLabel)]
+  struct Propagate;
+
+  #[derive(ScheduleLabel)]
+  struct Check;
+
+  fn at(x: f32, y: f32) -> (Transform, GlobalTransform) {
+    let transform = Transform {
+      position: Vec2::new(x, y),
+      ..Default::default()
+    };
+
+    (transform, GlobalTransform::default())
+  }
+
+  fn position(state: &State, entity: Entity) -> Vec2 {
+    state
+      .get::<Query<&GlobalTransform>>()
+      .get(entity)
+      .unwrap()
+      .translation()
+  }
+
+  #[test]
+  fn global_transforms_are_propagated_to_children() {
+    let mut state = State::default();
+    let character = state.spawn(at(10.0, 0.0));
+    let sword = state.spawn(at(12.0, 0.0));
+
+    assert!(state.set_parent(sword, character));
+    assert!(!state.set_parent(character, sword));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Propagate, transform::propagate_transforms);
+    scheduler.run(Propagate, &mut state);
+
+    assert_eq!(position(&state, sword), Vec2::new(12.0, 0.0));
+
+    state
+      .get::<Query<&mut Transform>>()
+      .get_mut(character)
+      .unwrap()
+      .rotation = std::f32::consts::FRAC_PI_2;
+    scheduler.run(Propagate, &mut state);
+
+    assert!(position(&state, sword).abs_diff_eq(Vec2::new(10.0, 2.0), 1e-5));
+  }
+
+  #[test]
+  fn reparenting_keeps_world_positions() {
+    let mut state = State::default();
+    let character = state.spawn(at(10.0, 0.0));
+    let panel = state.spawn(at(-5.0, 5.0));
+    let sword = state.spawn(at(12.0, 0.0));
+
+    state.set_parent(sword, character);
+    state.set_parent(sword, panel);
+
+    let transforms = state.get::<Query<&Transform>>();
+    let children = state.get::<Query<&Children>>();
+
+    assert_eq!(
+      transforms.get(sword).unwrap().position,
+      Vec2::new(17.0, -5.0)
+    );
+    assert_eq!(state.parent(sword), Some(panel));
+    assert!(children.get(character).unwrap().get().is_empty());
+    assert_eq!(children.get(panel).unwrap().get(), &[sword]);
+
+    drop((transforms, children));
+    state.remove_parent(sword);
+
+    assert_eq!(state.world_affine(sword).translation, Vec2::new(12.0, 0.0));
+    assert!(state.despawn_recursive(panel));
+    assert!(state.contains(sword));
+
+    state.set_parent(sword, character);
+    state.despawn_recursive(character);
+
+    assert!(!state.contains(sword));
+  }
+
+  #[test]
+  fn despawned_entities_are_detached_from_the_hierarchy() {
+    let mut state = State::default();
+    let character = state.spawn(at(10.0, 0.0));
+    let sword = state.spawn(at(12.0, 0.0));
+    let shield = state.spawn(at(8.0, 0.0));
+
+    state.set_parent(sword, character);
+    state.set_parent(shield, character);
+
+    assert!(state.despawn(sword));
+    assert_eq!(
+      state
+        .get::<Query<&Children>>()
+        .get(character)
+        .unwrap()
+        .get(),
+      &[shield]
+    );
+
+    assert!(state.despawn(character));
+    assert_eq!(state.parent(shield), None);
+    assert_eq!(state.world_affine(shield).translation, Vec2::new(8.0, 0.0));
+  }
+
+  #[test]
+  fn unchanged_global_transforms_are_not_marked_changed() {
+    let mut state = State::default();
+    state.add(Vec::<bool>::new());
+    let character = state.spawn(at(10.0, 0.0));
+    let sword = state.spawn(at(12.0, 0.0));
+    state.set_parent(sword, character);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Propagate, transform::propagate_transforms);
+    scheduler.add_handler(
+      Check,
+      |globals: Res<Components<GlobalTransform>>, mut changes: ResMut<Vec<bool>>| {
+        changes.push(globals.is_changed());
+      },
+    );
+
+    for _ in 0..2 {
+      scheduler.run(Propagate, &mut state);
+      scheduler.run(Check, &mut state);
+    }
+
+    assert_eq!(*state.get::<Res<Vec<bool>>>(), vec![true, false]);
+  }
+
+  #[test]
+  fn entities_without_transforms_do_not_move_their_children() {
+    let mut state = State::default();
+    let group = state.spawn(GlobalTransform::default());
+    let pivot = state.spawn(GlobalTransform::default());
+    let sword = state.spawn(at(12.0, 0.0));
+
+    state.set_parent(pivot, group);
+    state.set_parent(sword, pivot);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Propagate, transform::propagate_transforms);
+    scheduler.run(Propagate, &mut state);
+
+    assert_eq!(position(&state, sword), Vec2::new(12.0, 0.0));
+    assert_eq!(
+      position(&state, sword),
+      state.world_affine(sword).translation
+    );
+  }
+}
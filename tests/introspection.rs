@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{introspection, prelude::*};
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  enum Stage {
+    Simulate,
+  }
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  struct Level(u32);
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  enum Zone {
+    Area { id: u8 },
+  }
+
+  struct Position(f32);
+
+  struct Speed(f32);
+
+  fn accelerate(mut speed: ResMut<Speed>) {
+    speed.0 += 1.0;
+  }
+
+  fn movement(speed: Res<Speed>, mut position: ResMut<Position>) {
+    position.0 += speed.0;
+  }
+
+  fn scheduler() -> Scheduler {
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Stage::Simulate, movement.after("accelerate"));
+    scheduler.add_handler(Stage::Simulate, accelerate.label("accelerate"));
+
+    scheduler
+  }
+
+  #[test]
+  fn schedules_describe_their_handlers_in_order() {
+    let schedules = scheduler().describe().unwrap();
+    let handlers = schedules[0].handlers();
+
+    assert_eq!(schedules[0].name(), "introspection::tests::Stage::Simulate");
+    assert_eq!(handlers[0].name(), "introspection::tests::accelerate");
+    assert_eq!(handlers[1].name(), "introspection::tests::movement");
+    assert_eq!(handlers[1].dependencies(), &[0]);
+    assert_eq!(handlers[1].batch(), 1);
+    assert_eq!(handlers[1].reads(), &["introspection::tests::Speed"]);
+    assert_eq!(handlers[1].writes(), &["introspection::tests::Position"]);
+  }
+
+  #[test]
+  fn schedules_are_exported_as_a_listing_and_a_graph() {
+    let schedules = scheduler().describe().unwrap();
+
+    let listing = introspection::listing(&schedules);
+    let dot = introspection::dot(&schedules);
+
+    assert!(listing.contains("  batch 1\n    introspection::tests::movement\n"));
+    assert!(listing.contains("      after: introspection::tests::accelerate\n"));
+    assert!(dot.contains("  \"0_0\" -> \"0_1\";\n"));
+    assert!(
+      dot.contains("  \"introspection::tests::Speed\" -> \"0_1\" [style=dashed];\n")
+    );
+    assert!(
+      dot.contains("  \"0_1\" -> \"introspection::tests::Position\" [color=red];\n")
+    );
+  }
+
+  #[test]
+  fn schedules_are_named_after_the_value_of_their_label() {
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Level(1), accelerate);
+    scheduler.add_handler(Level(2), accelerate);
+    scheduler.add_handler(Zone::Area { id: 3 }, accelerate);
+
+    let schedules = scheduler.describe().unwrap();
+    let names: Vec<_> = schedules.iter().map(|schedule| schedule.name()).collect();
+
+    assert_eq!(
+      names,
+      vec![
+        "introspection::tests::Level(1)",
+        "introspection::tests::Level(2)",
+        "introspection::tests::Zone::Area { id: 3 }",
+      ]
+    );
+  }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{
+    binding::BindGroup,
+    materials::Material,
+    math::Rectangle,
+    mesh::{Mesh, MeshInstance, Visibility},
+    prelude::*,
+    resources::Resources,
+  };
+
+  use glam::Vec2;
+
+  struct Unlit;
+
+  impl Material for Unlit {
+    fn shader() -> &'static str {
+      "shaders/unlit.wgsl"
+    }
+
+    fn bind_group(&self) -> &BindGroup {
+      unreachable!("Materials are not rendered in tests.")
+    }
+  }
+
+  fn instance() -> MeshInstance<Unlit> {
+    MeshInstance {
+      mesh: Resources::<Mesh>::default().add(Rectangle::new(0.5, 0.5)),
+      material: Resources::<Unlit>::default().add(Unlit),
+      transform: Transform::default(),
+    }
+  }
+
+  #[test]
+  fn instance_transforms_are_mutated_through_handles() {
+    let mut state = State::default();
+    let mut commands = Commands::default();
+
+    let handle = commands.spawn(instance());
+    assert!(handle.entity().is_none());
+
+    commands.apply(&mut state);
+
+    let mut transforms = state.get::<Query<&mut Transform>>();
+    handle.transform_mut(&mut transforms).unwrap().position = Vec2::new(1.0, 2.0);
+    drop(transforms);
+
+    assert_eq!(
+      handle
+        .transform(&state.get::<Query<&Transform>>())
+        .unwrap()
+        .position,
+      Vec2::new(1.0, 2.0)
+    );
+  }
+
+  #[test]
+  fn instances_are_hidden_and_despawned_through_handles() {
+    let mut state = State::default();
+    let mut commands = Commands::default();
+
+    let character = commands.spawn(instance());
+    let sword = commands.spawn(instance());
+    character.set_visible(&mut commands, false);
+    commands.apply(&mut state);
+
+    let (character_entity, sword_entity) =
+      (character.entity().unwrap(), sword.entity().unwrap());
+    state.set_parent(sword_entity, character_entity);
+
+    let visibilities = state.get::<Query<&Visibility>>();
+    assert_eq!(character.is_visible(&visibilities), Some(false));
+    assert_eq!(sword.is_visible(&visibilities), Some(true));
+    drop(visibilities);
+
+    let mut commands = Commands::default();
+    character.despawn(&mut commands);
+    commands.apply(&mut state);
+
+    assert!(!state.contains(character_entity));
+    assert!(!state.contains(sword_entity));
+    assert_eq!(sword.is_visible(&state.get::<Query<&Visibility>>()), None);
+  }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+  use charbs::prelude::*;
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  struct Level(u32);
+
+  fn count(mut runs: ResMut<u32>) {
+    *runs += 1;
+  }
+
+  #[test]
+  fn handlers_are_only_timed_with_a_profiler() {
+    let mut state = State::default();
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, count);
+    scheduler.run(Update, &mut state);
+
+    state.add(Profiler::new(2));
+
+    for _ in 0..3 {
+      scheduler.run(Update, &mut state);
+    }
+
+    let profiler = state.get::<Res<Profiler>>();
+    let handler = profiler
+      .handler("profiling::tests::Update", "profiling::tests::count")
+      .unwrap();
+
+    assert_eq!(handler.len(), 2);
+    assert!(handler.max() >= handler.min());
+    assert_eq!(
+      profiler.schedule("profiling::tests::Update").unwrap().len(),
+      2
+    );
+    assert_eq!(profiler.trace_json(), "{\"traceEvents\":[]}");
+  }
+
+  #[test]
+  fn traces_are_exported_as_trace_events() {
+    let mut state = State::default();
+    state.add(0_u32);
+    state.add(Profiler::default());
+    state.get::<ResMut<Profiler>>().start_trace();
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, count);
+    scheduler.run(Update, &mut state);
+
+    let trace = state.get::<Res<Profiler>>().trace_json();
+
+    assert!(
+      trace.starts_with("{\"traceEvents\":[{\"name\":\"profiling::tests::Update\"")
+    );
+    assert!(
+      trace.contains(
+        "\"name\":\"profiling::tests::count\",\"cat\":\"handler\",\"ph\":\"X\""
+      )
+    );
+  }
+
+  #[test]
+  fn schedules_of_different_label_values_are_timed_separately() {
+    let mut state = State::default();
+    state.add(0_u32);
+    state.add(Profiler::default());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Level(1), count);
+    scheduler.add_handler(Level(2), count);
+
+    scheduler.run(Level(1), &mut state);
+    scheduler.run(Level(2), &mut state);
+    scheduler.run(Level(2), &mut state);
+
+    let profiler = state.get::<Res<Profiler>>();
+    let timings = |name| profiler.schedule(name).map(|timings| timings.len());
+
+    assert_eq!(timings("profiling::tests::Level(1)"), Some(1));
+    assert_eq!(timings("profiling::tests::Level(2)"), Some(2));
+    assert_eq!(timings("profiling::tests::Level"), None);
+  }
+}
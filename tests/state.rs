@@ -0,0 +1,362 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{prelude::*, state::ScheduleError};
+  use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+      Mutex,
+      mpsc::{Receiver, Sender, channel},
+    },
+    thread::ThreadId,
+    time::Duration,
+  };
+
+  #[derive(ScheduleLabel)]
+  struct Update;
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  enum Phase {
+    Early,
+    Late,
+  }
+
+  #[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash)]
+  struct Level(u32);
+
+  struct Counter(u32);
+
+  fn count(mut runs: ResMut<u32>) {
+    *runs += 1;
+  }
+
+  #[test]
+  fn optional_res_is_none_when_missing() {
+    let state = State::default();
+
+    assert!(state.get::<Option<Res<Counter>>>().is_none());
+    assert!(state.get::<Option<ResMut<Counter>>>().is_none());
+  }
+
+  #[test]
+  fn optional_res_mut_updates_existing_structure() {
+    let mut state = State::default();
+    state.add(Counter(0));
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, |counter: Option<ResMut<Counter>>| {
+      if let Some(mut counter) = counter {
+        counter.0 += 1;
+      }
+    });
+    scheduler.add_handler(
+      Update,
+      |missing: Option<Res<String>>, mut runs: ResMut<u32>| {
+        if missing.is_none() {
+          *runs += 1;
+        }
+      },
+    );
+
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(state.get::<Res<Counter>>().0, 2);
+    assert_eq!(*state.get::<Res<u32>>(), 2);
+  }
+
+  #[test]
+  fn structures_can_be_removed() {
+    let mut state = State::default();
+    state.add(Counter(3));
+
+    assert_eq!(state.remove::<Counter>().map(|counter| counter.0), Some(3));
+    assert!(state.remove::<Counter>().is_none());
+    assert!(!state.has::<Counter>());
+  }
+
+  fn push(value: u32) -> impl FnMut(ResMut<Vec<u32>>) {
+    move |mut order: ResMut<Vec<u32>>| order.push(value)
+  }
+
+  #[test]
+  fn handlers_respect_ordering_constraints() {
+    let mut state = State::default();
+    state.add(Vec::<u32>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, push(3).label("three").after("two"));
+    scheduler.add_handler(Update, push(1).in_set("early"));
+    scheduler.add_handler(Update, push(2).label("two").after("early"));
+    scheduler.add_handler(Update, push(0).before("early"));
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn handler_sets_can_be_ordered() {
+    let mut state = State::default();
+    state.add(Vec::<u32>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, push(2).in_set("late"));
+    scheduler.add_handler(Update, push(1).in_set("early"));
+    scheduler.configure_set(Update, HandlerSet::new("late").after("early"));
+
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![1, 2]);
+  }
+
+  #[test]
+  fn ordering_cycles_are_reported() {
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, push(0).label("a").after("c"));
+    scheduler.add_handler(Update, push(1).label("b").after("a"));
+    scheduler.add_handler(Update, push(2).label("c").after("b"));
+
+    let error = scheduler.build().unwrap_err();
+
+    assert_eq!(
+      error.to_string(),
+      "Handler ordering contains a cycle: \"a\" -> \"b\" -> \"c\" -> \"a\""
+    );
+  }
+
+  struct Channel {
+    sender: Sender<()>,
+    receiver: Mutex<Receiver<()>>,
+    received: bool,
+  }
+
+  struct Left(Channel);
+
+  struct Right(Channel);
+
+  fn channels() -> (Channel, Channel) {
+    let (left_sender, right_receiver) = channel();
+    let (right_sender, left_receiver) = channel();
+
+    let channel = |sender, receiver| Channel {
+      sender,
+      receiver: Mutex::new(receiver),
+      received: false,
+    };
+
+    (
+      channel(left_sender, left_receiver),
+      channel(right_sender, right_receiver),
+    )
+  }
+
+  fn exchange(channel: &mut Channel) {
+    channel.sender.send(()).unwrap();
+
+    let receiver = channel.receiver.lock().unwrap();
+    channel.received = receiver.recv_timeout(Duration::from_secs(5)).is_ok();
+  }
+
+  #[test]
+  fn handlers_with_disjoint_access_run_concurrently() {
+    let (left, right) = channels();
+
+    let mut state = State::default();
+    state.add(Left(left));
+    state.add(Right(right));
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, |mut left: ResMut<Left>| exchange(&mut left.0));
+    scheduler.add_handler(Update, |mut right: ResMut<Right>| exchange(&mut right.0));
+
+    scheduler.run(Update, &mut state);
+
+    assert!(state.get::<Res<Left>>().0.received);
+    assert!(state.get::<Res<Right>>().0.received);
+  }
+
+  #[test]
+  fn non_send_structures_are_accessed_from_their_thread() {
+    let mut state = State::default();
+    state.add_non_send(Rc::new(Cell::new(None::<ThreadId>)));
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, |thread: Res<Rc<Cell<Option<ThreadId>>>>| {
+      thread.set(Some(std::thread::current().id()));
+    });
+    scheduler.add_handler(Update, count);
+
+    scheduler.run(Update, &mut state);
+
+    let thread = state.get::<Res<Rc<Cell<Option<ThreadId>>>>>().get();
+
+    assert_eq!(thread, Some(std::thread::current().id()));
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  fn conflicting(_counter: Res<Counter>, _counter_mut: ResMut<Counter>) {}
+
+  fn write_counter(_counter: ResMut<Counter>) {}
+
+  #[test]
+  fn conflicting_accesses_are_reported_when_added() {
+    let mut scheduler = Scheduler::default();
+
+    let error = scheduler.try_add_handler(Update, conflicting).unwrap_err();
+
+    assert_eq!(
+      error,
+      ScheduleError::ConflictingAccess {
+        handler: "state::tests::conflicting".to_string(),
+        structure: "state::tests::Counter",
+      }
+    );
+  }
+
+  #[test]
+  fn conditions_do_not_conflict_with_their_handler() {
+    let mut scheduler = Scheduler::default();
+
+    let result = scheduler
+      .try_add_handler(Update, write_counter.run_if(resource_exists::<Counter>()));
+
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  #[should_panic(
+    expected = "has conflicting accesses to structure state::tests::Counter"
+  )]
+  fn adding_conflicting_handlers_panics() {
+    Scheduler::default().add_handler(Update, conflicting);
+  }
+
+  fn count_locally(mut local: Local<u32>, mut total: ResMut<Vec<u32>>) {
+    *local += 1;
+    total.push(*local);
+  }
+
+  #[test]
+  fn local_data_persists_per_handler() {
+    let mut state = State::default();
+    state.add(Vec::<u32>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, count_locally);
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+
+    scheduler.add_handler(Update, count_locally);
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![1, 2, 3, 1]);
+  }
+
+  fn reset(state: &mut State) {
+    state.add(Vec::<u32>::new());
+  }
+
+  fn record_changes(counter: Res<Counter>, mut changes: ResMut<Vec<(bool, bool)>>) {
+    changes.push((counter.is_added(), counter.is_changed()));
+  }
+
+  #[test]
+  fn changes_are_detected_since_the_last_run() {
+    let mut state = State::default();
+    state.add(Counter(0));
+    state.add(Vec::<(bool, bool)>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, record_changes);
+
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+
+    state.get::<ResMut<Counter>>().0 += 1;
+    scheduler.run(Update, &mut state);
+
+    let _ = state.get::<ResMut<Counter>>().0;
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(
+      *state.get::<Res<Vec<(bool, bool)>>>(),
+      vec![(true, true), (false, false), (false, true), (false, false)]
+    );
+  }
+
+  #[test]
+  fn exclusive_handlers_access_the_whole_state() {
+    let mut state = State::default();
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Update, push(1).after("reset"));
+    scheduler.add_handler(Update, push(0).before("reset"));
+    scheduler.add_handler(Update, reset.label("reset"));
+    scheduler.add_handler(Update, push(2).after("reset"));
+
+    state.add(Vec::<u32>::new());
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![1, 2]);
+  }
+
+  #[test]
+  fn exclusive_handlers_can_add_handlers() {
+    let mut state = State::default();
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(
+      Update,
+      |_: &mut State, scheduler: &mut Scheduler| -> Result<(), ScheduleError> {
+        scheduler.try_add_handler(Update, count)?;
+
+        Ok(())
+      },
+    );
+
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 0);
+
+    scheduler.run(Update, &mut state);
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+
+  #[test]
+  fn labels_are_identified_by_value() {
+    let mut state = State::default();
+    state.add(Vec::<u32>::new());
+
+    let mut scheduler = Scheduler::default();
+    scheduler.add_handler(Phase::Early, push(0));
+    scheduler.add_handler(Phase::Late, push(1));
+    scheduler.add_handler(Level(1), push(2));
+    scheduler.add_handler(Level(2), push(3));
+
+    scheduler.run(Phase::Late, &mut state);
+    scheduler.run(Level(2), &mut state);
+    scheduler.run(Phase::Early, &mut state);
+
+    assert_eq!(*state.get::<Res<Vec<u32>>>(), vec![1, 3, 0]);
+  }
+
+  #[test]
+  fn running_handlers_can_remove_other_handlers() {
+    let mut state = State::default();
+    state.add(0_u32);
+
+    let mut scheduler = Scheduler::default();
+    let id = scheduler.add_handler(Update, count);
+    scheduler.add_handler(Update, move |_: &mut State, scheduler: &mut Scheduler| {
+      scheduler.remove_handler(id)
+    });
+
+    scheduler.run(Update, &mut state);
+    scheduler.run(Update, &mut state);
+
+    assert_eq!(*state.get::<Res<u32>>(), 1);
+  }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+  use charbs::{events::*, prelude::*};
+  use std::{
+    any::Any,
+    sync::{
+      Mutex,
+      atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+  };
+
+  #[derive(Event, Debug, PartialEq)]
+  struct Finished;
+
+  #[test]
+  fn virtual_time_can_be_paused_and_scaled() {
+    let mut time = Time::default();
+
+    time.advance(Duration::from_millis(10));
+    time.set_scale(2.0);
+    time.advance(Duration::from_millis(10));
+    time.pause();
+    time.advance(Duration::from_millis(10));
+
+    assert_eq!(time.frame(), 3);
+    assert_eq!(time.delta(), Duration::ZERO);
+    assert_eq!(time.elapsed(), Duration::from_millis(30));
+    assert_eq!(time.real_delta(), Duration::from_millis(10));
+    assert_eq!(time.real_elapsed(), Duration::from_millis(30));
+  }
+
+  #[test]
+  fn repeating_timers_notify_every_completion() {
+    let mut timer = Timer::new(Duration::from_millis(10), TimerMode::Repeating);
+    let mut events = EventBus::default();
+
+    timer.tick_and_notify(Duration::from_millis(25), &mut events, || Finished);
+    assert_eq!(timer.elapsed(), Duration::from_millis(5));
+
+    timer.tick_and_notify(Duration::from_millis(4), &mut events, || Finished);
+    assert!(!timer.just_finished());
+
+    assert_eq!(events.read::<Finished>(), vec![Finished, Finished]);
+  }
+
+  #[test]
+  fn once_timers_and_stopwatches_stop() {
+    let mut timer = Timer::new(Duration::from_millis(10), TimerMode::Once);
+    let mut stopwatch = Stopwatch::default();
+
+    assert!(timer.tick(Duration::from_millis(15)).just_finished());
+    assert!(!timer.tick(Duration::from_millis(15)).just_finished());
+    assert!(timer.is_finished());
+
+    stopwatch.tick(Duration::from_millis(5));
+    stopwatch.pause();
+    stopwatch.tick(Duration::from_millis(5));
+
+    assert_eq!(stopwatch.elapsed(), Duration::from_millis(5));
+  }
+
+  #[test]
+  fn zero_duration_once_timers_finish_on_their_first_tick() {
+    let mut timer = Timer::new(Duration::ZERO, TimerMode::Once);
+    let mut events = EventBus::default();
+
+    assert!(!timer.is_finished());
+    timer.tick_and_notify(Duration::ZERO, &mut events, || Finished);
+    assert!(timer.just_finished() && timer.is_finished());
+
+    timer.tick_and_notify(Duration::from_millis(5), &mut events, || Finished);
+    assert!(!timer.just_finished());
+
+    assert_eq!(events.read::<Finished>(), vec![Finished]);
+  }
+
+  #[test]
+  fn fixed_time_runs_a_step_per_elapsed_step_duration() {
+    let mut time = FixedTime::new(Duration::from_millis(10));
+
+    assert_eq!(time.advance(Duration::from_millis(25)), 2);
+    assert_eq!(time.accumulator(), Duration::from_millis(5));
+    assert_eq!(time.alpha(), 0.5);
+
+    assert_eq!(time.advance(Duration::from_millis(5)), 1);
+    assert_eq!(time.accumulator(), Duration::ZERO);
+  }
+
+  #[test]
+  fn fixed_time_drops_steps_beyond_the_maximum() {
+    let mut time = FixedTime::new(Duration::from_millis(10)).with_max_steps(3);
+
+    assert_eq!(time.advance(Duration::from_millis(104)), 3);
+    assert_eq!(time.accumulator(), Duration::from_millis(4));
+  }
+
+  static FIXED_DELTAS: Mutex<Vec<Duration>> = Mutex::new(Vec::new());
+  static LAST: Mutex<(Duration, Duration)> = Mutex::new((Duration::ZERO, Duration::ZERO));
+
+  fn run_two_iterations(app: App) {
+    let step = Duration::from_millis(10);
+
+    app.run_main_schedules();
+    assert_eq!(
+      std::mem::take(&mut *FIXED_DELTAS.lock().unwrap()),
+      vec![step; 3]
+    );
+
+    app.run_main_schedules();
+    assert_eq!(
+      std::mem::take(&mut *FIXED_DELTAS.lock().unwrap()),
+      vec![step; 3]
+    );
+
+    let (delta, elapsed) = *LAST.lock().unwrap();
+    assert_eq!(delta, Duration::from_millis(104));
+    assert!(elapsed >= Duration::from_millis(139));
+  }
+
+  #[test]
+  fn fixed_updates_run_in_the_main_loop() {
+    App::default()
+      .add_state(FixedTime::new(Duration::from_millis(10)).with_max_steps(3))
+      .add_state(vec![Duration::from_millis(104), Duration::from_millis(35)])
+      .add_handler(
+        PreUpdate,
+        |mut time: ResMut<Time>, mut deltas: ResMut<Vec<Duration>>| {
+          time.advance(deltas.pop().unwrap());
+        },
+      )
+      .add_handler(FixedUpdate, |time: Res<Time>| {
+        FIXED_DELTAS.lock().unwrap().push(time.delta());
+      })
+      .add_handler(Last, |time: Res<Time>| {
+        *LAST.lock().unwrap() = (time.delta(), time.elapsed());
+      })
+      .set_runner(run_two_iterations)
+      .run();
+  }
+}